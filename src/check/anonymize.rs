@@ -0,0 +1,203 @@
+//! 报告脱敏：将主机名、IPv4/IPv6/MAC 地址、挂载源路径替换为稳定的哈希占位符
+//!
+//! 在序列化后的 JSON 值上统一做字符串替换，再反序列化回 CheckReport，
+//! 保证所有章节（日志、事件、权限列表等）处理一致，text/json 输出均生效。
+//! 主机名只按完整单词替换，避免 "db"、"app" 这类短名破坏镜像名、标签等。
+
+use std::collections::BTreeMap;
+use std::net::Ipv6Addr;
+use crate::check::report::CheckReport;
+use crate::utils::{Result, SedockerError};
+use regex::{NoExpand, Regex};
+
+pub fn apply(report: &CheckReport) -> Result<CheckReport> {
+    let mut mapping: BTreeMap<String, String> = BTreeMap::new();
+
+    // 已知标识
+    add(&mut mapping, &report.host.os.hostname, "host");
+    for c in &report.containers {
+        for n in &c.networks {
+            add(&mut mapping, &n.ip_address, "ip");
+            add(&mut mapping, &n.gateway, "ip");
+            add(&mut mapping, &n.mac_address, "mac");
+        }
+        for p in &c.ports {
+            if p.host_ip != "0.0.0.0" && p.host_ip != "::" {
+                add(&mut mapping, &p.host_ip, "ip");
+            }
+        }
+        for m in &c.mounts {
+            if m.source.starts_with('/') && m.source != "/" {
+                let placeholder = format!("/anon/path-{}", short_hash(&m.source));
+                mapping.entry(m.source.clone()).or_insert(placeholder);
+            }
+        }
+    }
+
+    let mut value = serde_json::to_value(report)
+        .map_err(|e| SedockerError::System(format!("JSON serialize: {}", e)))?;
+
+    // 其余字符串（日志、事件属性等）中出现的 IPv4 / IPv6 地址
+    collect_ips(&value, &mut mapping);
+
+    // 长的先替换，避免短值（如某路径前缀）破坏长值
+    let mut pairs: Vec<(&String, &String)> = mapping.iter().collect();
+    pairs.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+    let rules: Vec<(Regex, &String)> = pairs.into_iter()
+        .map(|(from, to)| (replace_pattern(from, from == &report.host.os.hostname), to))
+        .collect();
+    replace_strings(&mut value, &rules);
+
+    for (original, placeholder) in &mapping {
+        eprintln!("anonymize: {} <- {}", placeholder, original);
+    }
+
+    serde_json::from_value(value)
+        .map_err(|e| SedockerError::System(format!("JSON deserialize: {}", e)))
+}
+
+fn add(mapping: &mut BTreeMap<String, String>, original: &str, kind: &str) {
+    if original.is_empty() {
+        return;
+    }
+    mapping.entry(original.to_string())
+        .or_insert_with(|| format!("{}-{}", kind, short_hash(original)));
+}
+
+/// FNV-1a，跨运行稳定，便于对比多份脱敏报告
+fn short_hash(s: &str) -> String {
+    let mut h: u32 = 0x811c9dc5;
+    for b in s.bytes() {
+        h ^= b as u32;
+        h = h.wrapping_mul(0x01000193);
+    }
+    format!("{:06x}", h & 0xffffff)
+}
+
+/// 主机名按单词边界匹配；其余标识（地址、路径）按字面子串匹配
+fn replace_pattern(from: &str, whole_word: bool) -> Regex {
+    let escaped = regex::escape(from);
+    let pattern = if whole_word { format!(r"\b{}\b", escaped) } else { escaped };
+    Regex::new(&pattern).expect("escaped literal is a valid regex")
+}
+
+fn replace_strings(v: &mut serde_json::Value, rules: &[(Regex, &String)]) {
+    match v {
+        serde_json::Value::String(s) => {
+            for (from, to) in rules {
+                if from.is_match(s) {
+                    *s = from.replace_all(s, NoExpand(to)).into_owned();
+                }
+            }
+        }
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                replace_strings(item, rules);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            for (_, item) in obj.iter_mut() {
+                replace_strings(item, rules);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_ips(v: &serde_json::Value, mapping: &mut BTreeMap<String, String>) {
+    match v {
+        serde_json::Value::String(s) => {
+            for ip in find_ipv4(s) {
+                if ip != "0.0.0.0" && !ip.starts_with("127.") {
+                    add(mapping, &ip, "ip");
+                }
+            }
+            for ip in find_ipv6(s) {
+                add(mapping, &ip, "ip6");
+            }
+        }
+        serde_json::Value::Array(arr) => arr.iter().for_each(|i| collect_ips(i, mapping)),
+        serde_json::Value::Object(obj) => obj.values().for_each(|i| collect_ips(i, mapping)),
+        _ => {}
+    }
+}
+
+/// 在文本中查找 IPv6 地址：取十六进制 / ':' / '.' 组成的连续片段，用 Ipv6Addr 校验
+/// （排除 MAC、时间 12:34:56 等）；跳过 :: 与 ::1
+fn find_ipv6(s: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    for token in s.split(|c: char| !(c.is_ascii_hexdigit() || c == ':' || c == '.')) {
+        if token.matches(':').count() < 2 {
+            continue;
+        }
+        let token = token.trim_end_matches('.');
+        // 句末冒号（"fe80::1: error"）不属于地址，但 "fe80::" 的结尾 "::" 属于
+        let candidate = match token.strip_suffix(':') {
+            Some(t) if !token.ends_with("::") => t,
+            _ => token,
+        };
+        if let Ok(addr) = candidate.parse::<Ipv6Addr>() {
+            if !addr.is_unspecified() && !addr.is_loopback() {
+                found.push(candidate.to_string());
+            }
+        }
+    }
+    found
+}
+
+/// 在文本中查找形如 a.b.c.d（各段 0–255）的 IPv4 地址
+fn find_ipv4(s: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let at_boundary = i == 0 || !(bytes[i - 1].is_ascii_digit() || bytes[i - 1] == b'.');
+        if at_boundary && bytes[i].is_ascii_digit() {
+            let end = bytes[i..].iter()
+                .position(|b| !(b.is_ascii_digit() || *b == b'.'))
+                .map(|p| i + p)
+                .unwrap_or(bytes.len());
+            let candidate = s[i..end].trim_end_matches('.');
+            let octets: Vec<&str> = candidate.split('.').collect();
+            if octets.len() == 4
+                && octets.iter().all(|o| !o.is_empty() && o.len() <= 3 && o.parse::<u8>().is_ok())
+            {
+                found.push(candidate.to_string());
+            }
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_rules(s: &str, from: &str, to: &str, whole_word: bool) -> String {
+        let mut v = serde_json::Value::String(s.to_string());
+        let to = to.to_string();
+        replace_strings(&mut v, &[(replace_pattern(from, whole_word), &to)]);
+        v.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn hostname_matches_whole_words_only() {
+        assert_eq!(apply_rules("connected to db on port 5432", "db", "host-1", true),
+                   "connected to host-1 on port 5432");
+        assert_eq!(apply_rules("mongodb:7 / dbus / adbd", "db", "host-1", true),
+                   "mongodb:7 / dbus / adbd");
+        assert_eq!(apply_rules("web.example.com", "web", "host-1", true), "host-1.example.com");
+    }
+
+    #[test]
+    fn finds_ipv6_but_not_macs_or_times() {
+        assert_eq!(find_ipv6("listen [2001:db8::42]:8080, peer fe80::a00:27ff:fe4e:66a1%eth0"),
+                   vec!["2001:db8::42", "fe80::a00:27ff:fe4e:66a1"]);
+        assert_eq!(find_ipv6("neighbor fe80::1: unreachable"), vec!["fe80::1"]);
+        assert!(find_ipv6("mac 02:42:ac:11:00:02 at 12:34:56, bind :: and ::1").is_empty());
+        assert_eq!(find_ipv6("mapped ::ffff:10.0.0.5"), vec!["::ffff:10.0.0.5"]);
+    }
+}
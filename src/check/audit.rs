@@ -0,0 +1,817 @@
+//! 审计规则：基于已收集的 CheckReport 数据生成 findings，不做额外采集
+
+use crate::check::container::{ContainerInfo, FsChangeKind, MacKind};
+use crate::check::filter::COMPOSE_PROJECT_LABEL;
+use crate::check::overview::dangerous_caps;
+use crate::check::report::CheckReport;
+use crate::check::severity::{Finding, Severity};
+use crate::utils::time::{format_duration, seconds_since};
+
+/// inotify 使用量达到上限的该百分比时告警
+const INOTIFY_WARN_PERCENT: u64 = 90;
+
+/// 宿主机相对 NTP 的偏差超过该毫秒数时告警
+pub const HOST_CLOCK_SKEW_WARN_MS: f64 = 1000.0;
+
+/// 容器与宿主机时钟差超过该秒数时告警（容器侧只有整秒精度）
+pub const CONTAINER_CLOCK_SKEW_WARN_SECS: i64 = 5;
+
+/// 容器可写层中不应出现写入的系统目录
+pub const SYSTEM_WRITE_PATHS: &[&str] = &["/etc", "/usr", "/bin", "/sbin", "/lib", "/lib64", "/boot"];
+
+/// 常见数据库端口；暴露即值得确认是否配置了认证
+const DATABASE_PORTS: &[(u16, &str)] = &[
+    (3306, "mysql"), (5432, "postgres"), (6379, "redis"), (27017, "mongodb"),
+];
+
+/// 明确关闭认证的环境变量（KEY=VALUE，忽略大小写比较值）
+const NO_AUTH_ENV: &[(&str, &str)] = &[
+    ("POSTGRES_HOST_AUTH_METHOD", "trust"),
+    ("MYSQL_ALLOW_EMPTY_PASSWORD", "yes"),
+    ("MYSQL_ALLOW_EMPTY_PASSWORD", "true"),
+    ("MYSQL_ALLOW_EMPTY_PASSWORD", "1"),
+    ("ALLOW_EMPTY_PASSWORD", "yes"),
+];
+
+/// 经 --group-add 加入即近似获得宿主机 root 或敏感数据访问的组
+pub const SENSITIVE_GROUPS: &[&str] = &["root", "docker", "sudo", "wheel", "disk", "adm", "shadow"];
+
+/// 规则阈值（来自 CLI）
+pub struct AuditConfig {
+    pub crash_loop_restarts: i64,
+    pub crash_loop_uptime_secs: i64,
+    pub limits: LimitThresholds,
+    pub max_age_days: u64,
+    pub owner_label: String,
+}
+
+/// 用量占限制的百分比阈值（内存 / PID / CPU quota）
+#[derive(Debug, Clone, Copy)]
+pub struct LimitThresholds {
+    pub memory_percent: f64,
+    pub pids_percent: f64,
+    pub cpu_percent: f64,
+}
+
+/// 单项资源用量接近限制
+pub struct LimitBreach {
+    pub resource: &'static str,   // memory / pids / cpu
+    pub used_percent: f64,        // 用量占限制的百分比
+}
+
+pub fn run(report: &CheckReport, cfg: &AuditConfig) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    check_ip_forward(report, &mut findings);
+    check_inotify_exhaustion(report, &mut findings);
+    check_docker_socket(report, &mut findings);
+    check_docker_group(report, &mut findings);
+    check_host_clock_skew(report, &mut findings);
+    for c in &report.containers {
+        check_privileged(c, &mut findings);
+        check_oom_killed(c, &mut findings);
+        check_seccomp_unconfined(c, &mut findings);
+        check_compound_weakening(c, &mut findings);
+        check_world_writable_mounts(c, &mut findings);
+        check_crash_loop(c, cfg, &mut findings);
+        check_auto_remove(c, cfg, &mut findings);
+        check_missing_healthcheck(c, &mut findings);
+        check_stale_container(c, cfg.max_age_days, &mut findings);
+        check_limit_breaches(c, &cfg.limits, &mut findings);
+        check_system_writes(c, &mut findings);
+        check_group_add(c, &mut findings);
+        check_default_bridge(c, &mut findings);
+        check_container_clock_skew(c, &mut findings);
+        check_database_ports(c, &mut findings);
+        check_log_errors(c, &mut findings);
+        check_selinux_label(report, c, &mut findings);
+        check_effective_mac(report, c, &mut findings);
+        check_host_namespaces(c, &mut findings);
+        check_readonly_rootfs_bypass(c, &mut findings);
+        check_tmpfs_exec_suid(c, &mut findings);
+        check_proc_sys_mounts(c, &mut findings);
+        check_bind_sources(c, &mut findings);
+    }
+    assign_owners(&mut findings, report, &cfg.owner_label);
+    findings
+}
+
+/// 按容器标签填 owner：--owner-label 指定的键，其次 owner，最后 compose 项目名
+fn assign_owners(findings: &mut [Finding], report: &CheckReport, owner_label: &str) {
+    for f in findings.iter_mut() {
+        let c = match &f.container {
+            Some(name) => report.containers.iter().find(|c| &c.name == name),
+            None => None,
+        };
+        f.owner = c.and_then(|c| [owner_label, "owner", COMPOSE_PROJECT_LABEL].iter()
+            .find_map(|k| c.labels.get(*k).filter(|v| !v.is_empty()).cloned()));
+    }
+}
+
+// ── 宿主机规则 ──────────────────────────────────────────────────────────────
+
+/// ip_forward=0 时 bridge 网络的容器无法访问外部网络
+fn check_ip_forward(report: &CheckReport, out: &mut Vec<Finding>) {
+    if report.host.sysctls.get("net.ipv4.ip_forward").map(String::as_str) != Some("0") {
+        return;
+    }
+    let bridged: Vec<&str> = report.containers.iter()
+        .filter(|c| !matches!(c.network_mode.as_str(), "host" | "none")
+            && !c.network_mode.starts_with("container:"))
+        .map(|c| c.name.as_str())
+        .collect();
+    if bridged.is_empty() {
+        return;
+    }
+    out.push(Finding {
+        id: "host.ip_forward_disabled".to_string(),
+        severity: Severity::Medium,
+        container: None,
+        owner: None,
+        title: "net.ipv4.ip_forward=0 with bridge-networked containers".to_string(),
+        detail: format!("containers without outbound connectivity: {}", bridged.join(", ")),
+    });
+}
+
+/// 单个用户的 inotify 使用量接近 max_user_watches / max_user_instances
+fn check_inotify_exhaustion(report: &CheckReport, out: &mut Vec<Finding>) {
+    let ino = &report.host.inotify;
+    let uid = match ino.top_uid {
+        Some(u) => u,
+        None => return,
+    };
+    let near = |used: u64, max: u64| max > 0 && used * 100 >= max * INOTIFY_WARN_PERCENT;
+
+    if near(ino.top_uid_watches, ino.max_user_watches) {
+        out.push(Finding {
+            id: "host.inotify_watches".to_string(),
+            severity: Severity::Medium,
+            container: None,
+            owner: None,
+            title: "inotify watches near limit".to_string(),
+            detail: format!("uid {} uses {} of {} (fs.inotify.max_user_watches)",
+                uid, ino.top_uid_watches, ino.max_user_watches),
+        });
+    }
+    if near(ino.top_uid_instances, ino.max_user_instances) {
+        out.push(Finding {
+            id: "host.inotify_instances".to_string(),
+            severity: Severity::Medium,
+            container: None,
+            owner: None,
+            title: "inotify instances near limit".to_string(),
+            detail: format!("uid {} uses {} of {} (fs.inotify.max_user_instances)",
+                uid, ino.top_uid_instances, ino.max_user_instances),
+        });
+    }
+}
+
+/// docker.sock 对其他用户可读写，或属组不是 root/docker：任何能连上的用户都等同 root
+fn check_docker_socket(report: &CheckReport, out: &mut Vec<Finding>) {
+    let sock = match &report.host.docker_socket {
+        Some(s) => s,
+        None => return,
+    };
+    let (severity, reason) = if sock.mode & 0o006 != 0 {
+        (Severity::Critical, "is accessible to every local user")
+    } else if sock.mode & 0o060 != 0 && !matches!(sock.group.as_str(), "root" | "docker") {
+        (Severity::High, "is accessible to a non-docker group")
+    } else {
+        return;
+    };
+    out.push(Finding {
+        id: "host.docker_socket_permissive".to_string(),
+        severity,
+        container: None,
+        owner: None,
+        title: "docker socket permissions too broad".to_string(),
+        detail: format!("{} ({}:{} {:o}) {}; access to the socket is root on the host",
+            sock.path, sock.owner, sock.group, sock.mode, reason),
+    });
+}
+
+/// 普通登录账户在 docker 组中：无需 sudo 即可取得宿主机 root
+fn check_docker_group(report: &CheckReport, out: &mut Vec<Finding>) {
+    let users: Vec<&str> = report.host.docker_group.iter()
+        .filter(|m| m.interactive)
+        .map(|m| m.name.as_str())
+        .collect();
+    if users.is_empty() {
+        return;
+    }
+    out.push(Finding {
+        id: "host.docker_group_users".to_string(),
+        severity: Severity::High,
+        container: None,
+        owner: None,
+        title: "login accounts in the docker group".to_string(),
+        detail: format!("{}: docker group membership is root-equivalent (docker run -v /:/host)", users.join(", ")),
+    });
+}
+
+/// 宿主机时钟偏离 NTP：TLS 证书校验、Kerberos / JWT 等认证会失败
+fn check_host_clock_skew(report: &CheckReport, out: &mut Vec<Finding>) {
+    let Some(offset) = report.host.time.ntp_offset_ms else { return };
+    if offset.abs() < HOST_CLOCK_SKEW_WARN_MS {
+        return;
+    }
+    out.push(Finding {
+        id: "host.clock_skew".to_string(),
+        severity: Severity::Medium,
+        container: None,
+        owner: None,
+        title: "host clock is off from NTP".to_string(),
+        detail: format!("offset {:+.0}ms (threshold {}ms)", offset, HOST_CLOCK_SKEW_WARN_MS),
+    });
+}
+
+// ── 容器规则 ────────────────────────────────────────────────────────────────
+
+/// --privileged：全部 capability + 全部设备，可直接逃逸
+fn check_privileged(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    if !c.security.privileged {
+        return;
+    }
+    out.push(Finding {
+        id: "container.privileged".to_string(),
+        severity: Severity::Critical,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "running privileged".to_string(),
+        detail: "all capabilities and host devices; equivalent to root on the host".to_string(),
+    });
+}
+
+/// 上一次退出由 OOM killer 造成（State.OOMKilled）
+fn check_oom_killed(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    if !c.oom_killed {
+        return;
+    }
+    out.push(Finding {
+        id: "container.oom_killed".to_string(),
+        severity: Severity::High,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "last exit was an OOM kill".to_string(),
+        detail: format!("exit code {}, restarted {} times", c.exit_code, c.restart_count),
+    });
+}
+
+/// seccomp=unconfined：容器可调用全部 syscall
+fn check_seccomp_unconfined(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    if c.security.seccomp_profile != "unconfined" || c.security.privileged {
+        return;
+    }
+    // 同时加了危险 capability 时由组合规则统一报告
+    if !dangerous_caps(&c.security).is_empty() {
+        return;
+    }
+    out.push(Finding {
+        id: "container.seccomp_unconfined".to_string(),
+        severity: Severity::Medium,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "seccomp disabled".to_string(),
+        detail: "security-opt seccomp=unconfined exposes every syscall to the container".to_string(),
+    });
+}
+
+/// seccomp / AppArmor 关闭且加入危险 capability：单项尚可，叠加即可轻易逃逸，合并为一条
+fn check_compound_weakening(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let sec = &c.security;
+    if sec.privileged {
+        return;
+    }
+    let caps = dangerous_caps(sec);
+    if caps.is_empty() {
+        return;
+    }
+    let mut disabled = Vec::new();
+    if sec.seccomp_profile == "unconfined" {
+        disabled.push("seccomp unconfined");
+    }
+    if sec.apparmor_profile == "unconfined" || sec.applied_apparmor_profile == "unconfined"
+        || sec.effective_apparmor.as_deref().map(MacKind::of_apparmor) == Some(MacKind::Unconfined)
+    {
+        disabled.push("apparmor unconfined");
+    }
+    if disabled.is_empty() {
+        return;
+    }
+    // 两道防线都关闭且有 SYS_ADMIN / ALL 时再升一级
+    let admin = caps.iter().any(|c| c == "SYS_ADMIN" || c == "ALL");
+    let severity = if disabled.len() > 1 && admin { Severity::Critical } else { Severity::High };
+    out.push(Finding {
+        id: "container.compound_weakening".to_string(),
+        severity,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "confinement disabled with dangerous capabilities".to_string(),
+        detail: format!("{} + {} added = trivial escape", disabled.join(" + "), caps.join(", ")),
+    });
+}
+
+/// 挂载源下存在其他用户可写的文件/目录（忽略符号链接与带 sticky 位的目录）
+fn check_world_writable_mounts(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let mut hits = Vec::new();
+    for m in &c.mounts {
+        let n = m.permissions.iter()
+            .filter(|p| p.mode & libc::S_IFMT != libc::S_IFLNK
+                && p.mode & 0o002 != 0
+                && !(p.mode & libc::S_IFMT == libc::S_IFDIR && p.mode & 0o1000 != 0))
+            .count();
+        if n > 0 {
+            hits.push(format!("{} ({} entries)", m.source, n));
+        }
+    }
+    if hits.is_empty() {
+        return;
+    }
+    out.push(Finding {
+        id: "container.world_writable_mount".to_string(),
+        severity: Severity::Medium,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "world-writable files in mounted paths".to_string(),
+        detail: hits.join(", "),
+    });
+}
+
+/// 重启次数高且刚刚启动 → 疑似 crash loop
+fn check_crash_loop(c: &ContainerInfo, cfg: &AuditConfig, out: &mut Vec<Finding>) {
+    if c.restart_count <= cfg.crash_loop_restarts {
+        return;
+    }
+    let uptime = match seconds_since(&c.started_at) {
+        Some(s) => s,
+        None => return,
+    };
+    if uptime >= cfg.crash_loop_uptime_secs {
+        return;
+    }
+
+    let mut detail = format!("{} restarts, up {}s, last exit code {}",
+        c.restart_count, uptime, c.exit_code);
+    if let Some(logs) = &c.log_tail {
+        let start = logs.len().saturating_sub(3);
+        for line in &logs[start..] {
+            detail.push('\n');
+            detail.push_str(line);
+        }
+    }
+
+    out.push(Finding {
+        id: "container.crash_loop".to_string(),
+        severity: Severity::High,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "possible crash loop".to_string(),
+        detail,
+    });
+}
+
+/// --rm 与重启策略或反复崩溃同时出现：容器退出即被删除，OOM/崩溃后无法 inspect 或看日志
+fn check_auto_remove(c: &ContainerInfo, cfg: &AuditConfig, out: &mut Vec<Finding>) {
+    if !c.auto_remove {
+        return;
+    }
+    let mut reasons = Vec::new();
+    if !c.restart_policy.is_empty() && c.restart_policy != "no" {
+        reasons.push(format!("restart policy {}", c.restart_policy));
+    }
+    if c.restart_count > cfg.crash_loop_restarts {
+        reasons.push(format!("{} restarts", c.restart_count));
+    }
+    if reasons.is_empty() {
+        return;
+    }
+    out.push(Finding {
+        id: "container.auto_remove".to_string(),
+        severity: Severity::Low,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "auto-removed on exit; no post-mortem after a crash".to_string(),
+        detail: format!("--rm with {}: state, exit code and logs vanish when it stops", reasons.join(", ")),
+    });
+}
+
+/// 暴露了数据库端口：未发布的端口同网络容器仍可直连；环境变量显式关闭认证时升为 Medium
+/// （env 仅 --verbose 采集，否则只给 Info 提示）
+fn check_database_ports(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let no_auth = c.env.iter()
+        .filter_map(|e| e.split_once('='))
+        .find(|(k, v)| NO_AUTH_ENV.iter().any(|(nk, nv)| k == nk && v.eq_ignore_ascii_case(nv)));
+    for spec in &c.exposed_ports {
+        let port = crate::check::collector::port_number(spec);
+        let Some((_, db)) = DATABASE_PORTS.iter().find(|(p, _)| *p == port) else { continue };
+        let reach = if is_published(c, spec) { "published to the host" } else { "network-internal" };
+        let (severity, detail) = match no_auth {
+            Some((k, v)) => (Severity::Medium, format!("{} {} ({}), authentication disabled by {}={}", db, spec, reach, k, v)),
+            None => (Severity::Info, format!("{} {} ({}); confirm authentication is configured", db, spec, reach)),
+        };
+        out.push(Finding {
+            id: "container.database_port".to_string(),
+            severity,
+            container: Some(c.name.clone()),
+            owner: None,
+            title: "database port exposed".to_string(),
+            detail,
+        });
+    }
+}
+
+/// 该 ExposedPorts 项（"5432/tcp"）是否有宿主机端口绑定
+pub fn is_published(c: &ContainerInfo, spec: &str) -> bool {
+    let (port, proto) = spec.split_once('/').unwrap_or((spec, "tcp"));
+    c.ports.iter().any(|p| p.container_port == port && p.protocol == proto && !p.host_port.is_empty())
+}
+
+/// 运行中的容器没有健康检查：进程挂起但未退出时 docker 与编排层都无从察觉
+fn check_missing_healthcheck(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    if c.status != "running" || c.healthcheck_config.is_some() {
+        return;
+    }
+    out.push(Finding {
+        id: "container.no_healthcheck".to_string(),
+        severity: Severity::Info,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "no healthcheck configured".to_string(),
+        detail: "hung-but-running processes go unnoticed; add HEALTHCHECK or --health-cmd".to_string(),
+    });
+}
+
+/// 用量接近 resource_config 中的限制：内存即将 OOM、PID 即将 fork 失败、CPU 被 quota 压住
+fn check_limit_breaches(c: &ContainerInfo, t: &LimitThresholds, out: &mut Vec<Finding>) {
+    for b in limit_breaches(c, t) {
+        let (severity, title) = match b.resource {
+            "memory" => (Severity::High, "memory usage near limit"),
+            "pids"   => (Severity::Medium, "process count near pids limit"),
+            _        => (Severity::Low, "CPU usage pinned at quota"),
+        };
+        out.push(Finding {
+            id: format!("container.{}_near_limit", b.resource),
+            severity,
+            container: Some(c.name.clone()),
+            owner: None,
+            title: title.to_string(),
+            detail: format!("{} at {:.1}% of its limit", b.resource, b.used_percent),
+        });
+    }
+}
+
+/// 仅比较设置了限制的资源；无 stats 时为空
+pub fn limit_breaches(c: &ContainerInfo, t: &LimitThresholds) -> Vec<LimitBreach> {
+    let (rc, u) = match &c.resource_usage {
+        Some(u) => (&c.resource_config, u),
+        None => return vec![],
+    };
+    let mut out = Vec::new();
+    if rc.memory_limit > 0 {
+        let pct = u.memory_usage as f64 * 100.0 / rc.memory_limit as f64;
+        if pct >= t.memory_percent {
+            out.push(LimitBreach { resource: "memory", used_percent: pct });
+        }
+    }
+    if rc.pids_limit > 0 {
+        let pct = u.pids as f64 * 100.0 / rc.pids_limit as f64;
+        if pct >= t.pids_percent {
+            out.push(LimitBreach { resource: "pids", used_percent: pct });
+        }
+    }
+    if rc.cpu_quota > 0 && rc.cpu_period > 0 {
+        // docker stats 的 CPU% 以单核为 100%；quota/period 为允许的核数
+        let allowed = rc.cpu_quota as f64 * 100.0 / rc.cpu_period as f64;
+        let pct = u.cpu_percent * 100.0 / allowed;
+        if pct >= t.cpu_percent {
+            out.push(LimitBreach { resource: "cpu", used_percent: pct });
+        }
+    }
+    out
+}
+
+/// 长期未重新部署的运行中容器，镜像很可能已过时
+fn check_stale_container(c: &ContainerInfo, max_age_days: u64, out: &mut Vec<Finding>) {
+    let age = match c.age_seconds {
+        Some(a) if c.status == "running" && max_age_days > 0 && a >= max_age_days * 86400 => a,
+        _ => return,
+    };
+    let mut detail = format!("created {} ago", format_duration(age));
+    if let Some(img) = c.image_age_seconds {
+        detail.push_str(&format!(", image {} built {} ago", c.image, format_duration(img)));
+    }
+    out.push(Finding {
+        id: "container.stale".to_string(),
+        severity: Severity::Low,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: format!("running container older than {} days", max_age_days),
+        detail,
+    });
+}
+
+/// 可写层中对系统目录的新增/修改（依赖 --diff）
+fn check_system_writes(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let paths: Vec<&str> = c.fs_changes.iter()
+        .filter(|f| f.kind != FsChangeKind::Deleted && is_system_path(&f.path))
+        .map(|f| f.path.as_str())
+        .collect();
+    if paths.is_empty() {
+        return;
+    }
+
+    let mut detail = format!("{} added/changed paths under system directories", paths.len());
+    for p in paths.iter().take(5) {
+        detail.push('\n');
+        detail.push_str(p);
+    }
+    if paths.len() > 5 {
+        detail.push_str(&format!("\n... and {} more", paths.len() - 5));
+    }
+
+    out.push(Finding {
+        id: "container.system_path_writes".to_string(),
+        severity: Severity::Medium,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "writes to system paths in writable layer".to_string(),
+        detail,
+    });
+}
+
+/// --group-add 加入敏感组
+fn check_group_add(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let sensitive: Vec<String> = c.security.group_add.iter()
+        .filter_map(|g| sensitive_group_name(g).map(|name| {
+            if name == *g { name } else { format!("{} ({})", g, name) }
+        }))
+        .collect();
+    if sensitive.is_empty() {
+        return;
+    }
+    out.push(Finding {
+        id: "container.sensitive_group".to_string(),
+        severity: Severity::High,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "sensitive supplementary groups added".to_string(),
+        detail: format!("--group-add {}; membership in docker/disk is effectively root on the host",
+            sensitive.join(", ")),
+    });
+}
+
+/// 默认 bridge 网络上的容器彼此直接可达（无 DNS、无网络隔离）
+fn check_default_bridge(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    if matches!(c.network_mode.as_str(), "host" | "none") {
+        return;
+    }
+    if !c.networks.iter().any(|n| n.network_name == "bridge") {
+        return;
+    }
+    out.push(Finding {
+        id: "container.default_bridge".to_string(),
+        severity: Severity::Low,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "attached to the default bridge network".to_string(),
+        detail: "all containers on the default bridge can reach each other; use a user-defined network for isolation".to_string(),
+    });
+}
+
+/// 容器内时间与宿主机不一致（libfaketime、time namespace 等）
+fn check_container_clock_skew(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let Some(clock) = &c.clock else { return };
+    if clock.skew_seconds.abs() < CONTAINER_CLOCK_SKEW_WARN_SECS {
+        return;
+    }
+    out.push(Finding {
+        id: "container.clock_skew".to_string(),
+        severity: Severity::Medium,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "container clock differs from host".to_string(),
+        detail: format!("{:+}s vs host (threshold {}s)", clock.skew_seconds, CONTAINER_CLOCK_SKEW_WARN_SECS),
+    });
+}
+
+/// 日志中命中故障特征
+fn check_log_errors(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    if c.log_findings.is_empty() {
+        return;
+    }
+    let summary: Vec<String> = c.log_findings.iter()
+        .map(|f| format!("{} ×{}", f.pattern, f.count))
+        .collect();
+    out.push(Finding {
+        id: "container.log_errors".to_string(),
+        severity: Severity::Low,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "error patterns in logs".to_string(),
+        detail: summary.join(", "),
+    });
+}
+
+/// 宿主机 SELinux enforcing，但容器没有进程标签（如 label=disable）
+fn check_selinux_label(report: &CheckReport, c: &ContainerInfo, out: &mut Vec<Finding>) {
+    if report.host.security.selinux != "enforcing" || !c.security.process_label.is_empty() {
+        return;
+    }
+    out.push(Finding {
+        id: "container.no_selinux_label".to_string(),
+        severity: Severity::Medium,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "no SELinux label on an enforcing host".to_string(),
+        detail: "container runs without a process label (label=disable?), so SELinux does not confine it".to_string(),
+    });
+}
+
+/// 主进程实际运行在未约束的 MAC 域中（/proc/<pid>/attr），无论 SecurityOpt 如何配置
+fn check_effective_mac(report: &CheckReport, c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let sec = &c.security;
+    if sec.privileged {
+        return;
+    }
+    let mut unconfined = Vec::new();
+    // 同时加了危险 capability 时 AppArmor 由组合规则报告
+    if let Some(label) = sec.effective_apparmor.as_deref() {
+        if report.host.security.apparmor == "enabled" && MacKind::of_apparmor(label) == MacKind::Unconfined
+            && dangerous_caps(sec).is_empty()
+        {
+            unconfined.push(format!("AppArmor {}", label));
+        }
+    }
+    if let Some(context) = sec.effective_selinux.as_deref() {
+        if report.host.security.selinux == "enforcing" && MacKind::of_selinux(context) == MacKind::Unconfined {
+            unconfined.push(format!("SELinux {}", context));
+        }
+    }
+    if unconfined.is_empty() {
+        return;
+    }
+    out.push(Finding {
+        id: "container.mac_unconfined".to_string(),
+        severity: Severity::Medium,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "main process not confined by MAC".to_string(),
+        detail: format!("effective {}", unconfined.join(", ")),
+    });
+}
+
+/// 与宿主机共享 network / pid / ipc 命名空间
+fn check_host_namespaces(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let checks = [
+        (c.network_mode.as_str(), "network", Severity::Medium, "binds host interfaces and can reach services on localhost"),
+        (c.security.pid_mode.as_str(), "PID", Severity::High, "sees and can signal every host process"),
+        (c.security.ipc_mode.as_str(), "IPC", Severity::Medium, "shares host shared memory and semaphores"),
+    ];
+    for (mode, ns, severity, impact) in checks {
+        if mode == "host" {
+            out.push(Finding {
+                id: format!("container.host_{}_namespace", ns.to_lowercase()),
+                severity,
+                container: Some(c.name.clone()),
+                owner: None,
+                title: format!("shares the host {} namespace", ns),
+                detail: format!("{}={}: container {}", ns.to_lowercase(), mode, impact),
+            });
+        }
+    }
+}
+
+/// 只读 rootfs 下仍有可写挂载；覆盖 / 、/var 或系统目录时削弱最大
+fn check_readonly_rootfs_bypass(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    if !c.security.read_only_rootfs {
+        return;
+    }
+    let writable: Vec<_> = c.mounts.iter().filter(|m| m.rw).collect();
+    if writable.is_empty() {
+        return;
+    }
+    let is_key = |dest: &str| dest == "/" || dest == "/var" || is_system_path(dest);
+    let severity = if writable.iter().any(|m| is_key(&m.destination)) {
+        Severity::Medium
+    } else {
+        Severity::Low
+    };
+
+    let mut detail = format!("{} writable mount(s) on a read-only rootfs", writable.len());
+    for m in &writable {
+        detail.push_str(&format!("\n{} ({}){}", m.destination, m.mount_type,
+            if is_key(&m.destination) { "  ← key path" } else { "" }));
+    }
+    out.push(Finding {
+        id: "container.readonly_rootfs_bypass".to_string(),
+        severity,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "rootfs read-only but writable mount present".to_string(),
+        detail,
+    });
+}
+
+/// --tmpfs 同时 exec + suid：可在内存盘上投放并运行 setuid 程序
+fn check_tmpfs_exec_suid(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let risky: Vec<&str> = c.mounts.iter()
+        .filter(|m| m.tmpfs.as_ref().is_some_and(|t| t.exec && t.suid))
+        .map(|m| m.destination.as_str())
+        .collect();
+    if risky.is_empty() {
+        return;
+    }
+    out.push(Finding {
+        id: "container.tmpfs_exec_suid".to_string(),
+        severity: Severity::Medium,
+        container: Some(c.name.clone()),
+        owner: None,
+        title: "tmpfs mounted exec and suid".to_string(),
+        detail: format!("{}: setuid binaries written here can run; add noexec,nosuid", risky.join(", ")),
+    });
+}
+
+/// 宿主机 /proc、/sys（或其子路径）被挂入容器：可读写内核状态；rw 为高危，ro 仍泄露信息
+fn check_proc_sys_mounts(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    let kernel_fs = |p: &str| ["/proc", "/sys"].iter()
+        .any(|k| p == *k || p.strip_prefix(k).is_some_and(|rest| rest.starts_with('/')));
+    let (rw, ro): (Vec<_>, Vec<_>) = c.mounts.iter()
+        .filter(|m| m.mount_type == "bind" && (kernel_fs(&m.source) || kernel_fs(&m.destination)))
+        .partition(|m| m.rw);
+
+    for (mounts, writable) in [(rw, true), (ro, false)] {
+        if mounts.is_empty() {
+            continue;
+        }
+        let list: Vec<String> = mounts.iter()
+            .map(|m| format!("{} → {}", m.source, m.destination))
+            .collect();
+        out.push(Finding {
+            id: if writable { "container.kernel_fs_mount_rw" } else { "container.kernel_fs_mount_ro" }.to_string(),
+            severity: if writable { Severity::High } else { Severity::Medium },
+            container: Some(c.name.clone()),
+            owner: None,
+            title: format!("host /proc or /sys mounted {}", if writable { "read-write" } else { "read-only" }),
+            detail: format!("{}; {}", list.join(", "),
+                if writable { "container can modify kernel and cgroup settings" }
+                else { "container can read host kernel and process state" }),
+        });
+    }
+}
+
+/// bind 源已被删除，或目标像文件而源是目录（-v 指向不存在的文件时 docker 自动创建了空目录）
+fn check_bind_sources(c: &ContainerInfo, out: &mut Vec<Finding>) {
+    for m in c.mounts.iter().filter(|m| m.mount_type == "bind") {
+        let (id, title, detail) = if m.source_type == "missing" {
+            ("container.bind_source_missing", "bind mount source does not exist",
+                format!("{} → {}: host path is gone; the container sees an empty or stale mount", m.source, m.destination))
+        } else if m.source_type == "dir" && looks_like_file(&m.destination) {
+            ("container.bind_source_type_mismatch", "bind mount source is a directory but target looks like a file",
+                format!("{} → {}: docker creates a directory when the host file is missing; check the host path", m.source, m.destination))
+        } else {
+            continue;
+        };
+        out.push(Finding {
+            id: id.to_string(),
+            severity: Severity::Medium,
+            container: Some(c.name.clone()),
+            owner: None,
+            title: title.to_string(),
+            detail,
+        });
+    }
+}
+
+/// 按扩展名判断挂载目标是否应为文件
+pub fn looks_like_file(path: &str) -> bool {
+    const FILE_EXTS: &[&str] = &[
+        "conf", "cfg", "ini", "json", "yml", "yaml", "toml", "xml", "env", "properties",
+        "pem", "crt", "key", "sock", "txt", "sh", "py", "lua",
+    ];
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.')
+        .is_some_and(|(stem, ext)| !stem.is_empty() && FILE_EXTS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// 组名直接匹配；数字 GID 按宿主机 /etc/group 解析（bind 进来的 socket/设备按宿主机 GID 鉴权）
+pub fn sensitive_group_name(group: &str) -> Option<String> {
+    let name = match group.parse::<u32>() {
+        Ok(0) => "root".to_string(),
+        Ok(gid) => host_group_name(gid)?,
+        Err(_) => group.to_string(),
+    };
+    SENSITIVE_GROUPS.contains(&name.as_str()).then_some(name)
+}
+
+fn host_group_name(gid: u32) -> Option<String> {
+    let content = std::fs::read_to_string("/etc/group").ok()?;
+    content.lines().find_map(|l| {
+        let parts: Vec<&str> = l.split(':').collect();
+        (parts.get(2)?.parse::<u32>().ok()? == gid).then(|| parts[0].to_string())
+    })
+}
+
+pub fn is_system_path(path: &str) -> bool {
+    SYSTEM_WRITE_PATHS.iter().any(|p| {
+        path == *p || path.strip_prefix(p).is_some_and(|rest| rest.starts_with('/'))
+    })
+}
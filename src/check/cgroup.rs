@@ -0,0 +1,241 @@
+//! 直接读取容器 cgroup 统计（不经过 docker stats）
+//! 容器 cgroup 路径由主进程 /proc/<pid>/cgroup 解析，v1 / v2 布局均支持
+
+use crate::check::container::MemoryBreakdown;
+use crate::check::host;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// 主进程的 /proc/<pid>/cgroup 内容
+fn proc_cgroup(pid: i32) -> Option<String> {
+    if pid <= 0 {
+        return None;
+    }
+    fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()
+}
+
+/// 某个控制器下的 cgroup 目录，按 cgroup_version_at(root) 区分布局：
+/// v2 统一层级取 "0::/system.slice/docker-<id>.scope"；
+/// v1 按控制器分层取 "4:cpu,cpuacct:/docker/<id>"，挂载点为合并名或单控制器名的符号链接
+fn controller_dir(root: &Path, cgroup: &str, controller: &str) -> Option<PathBuf> {
+    let dir = if host::cgroup_version_at(root) == "v2" {
+        let rel = cgroup.lines().find_map(|l| l.strip_prefix("0::"))?;
+        root.join(rel.trim_start_matches('/'))
+    } else {
+        let (controllers, rel) = cgroup.lines().find_map(|l| {
+            let mut parts = l.splitn(3, ':');
+            let (_, controllers, path) = (parts.next()?, parts.next()?, parts.next()?);
+            controllers.split(',').any(|c| c == controller).then_some((controllers, path))
+        })?;
+        let mount = [controllers, controller].iter()
+            .map(|m| root.join(m))
+            .find(|d| d.is_dir())?;
+        mount.join(rel.trim_start_matches('/'))
+    };
+    dir.is_dir().then_some(dir)
+}
+
+/// memory.stat 中的常驻内存 / 页缓存：v2 为 anon / file，v1 为 total_rss / total_cache（退回 rss / cache）
+pub fn memory_breakdown(pid: i32) -> Option<MemoryBreakdown> {
+    memory_breakdown_at(Path::new(CGROUP_ROOT), &proc_cgroup(pid)?)
+}
+
+fn memory_breakdown_at(root: &Path, cgroup: &str) -> Option<MemoryBreakdown> {
+    let dir = controller_dir(root, cgroup, "memory")?;
+    let stat = fs::read_to_string(dir.join("memory.stat")).ok()?;
+    let field = |names: &[&str]| names.iter().find_map(|n| {
+        stat.lines()
+            .find_map(|l| l.strip_prefix(n).and_then(|r| r.strip_prefix(' ')))
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    });
+    Some(MemoryBreakdown {
+        rss:   field(&["anon", "total_rss", "rss"])?,
+        cache: field(&["file", "total_cache", "cache"])?,
+    })
+}
+
+/// 扫描 /proc/*/cgroup，找出 cgroup 路径包含容器完整 ID 的所有进程
+/// （v1 "/docker/<id>"、v2 "docker-<id>.scope" 均适用）
+pub fn container_pids(container_id: &str) -> Vec<i32> {
+    let entries = match fs::read_dir("/proc") {
+        Ok(e) => e,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut pids: Vec<i32> = entries
+        .flatten()
+        .filter_map(|e| e.file_name().to_str()?.parse::<i32>().ok())
+        .filter(|pid| {
+            fs::read_to_string(format!("/proc/{}/cgroup", pid))
+                .map(|c| c.contains(container_id))
+                .unwrap_or(false)
+        })
+        .collect();
+    pids.sort_unstable();
+    pids
+}
+
+/// 容器累计 CPU 时间（微秒）：v2 为 cpu.stat 的 usage_usec，v1 为 cpuacct.usage（纳秒）
+pub fn cpu_usage_usec(pid: i32) -> Option<u64> {
+    cpu_usage_usec_at(Path::new(CGROUP_ROOT), &proc_cgroup(pid)?)
+}
+
+fn cpu_usage_usec_at(root: &Path, cgroup: &str) -> Option<u64> {
+    // v2 忽略控制器名，取统一层级目录
+    let dir = controller_dir(root, cgroup, "cpuacct")?;
+    if let Ok(ns) = fs::read_to_string(dir.join("cpuacct.usage")) {
+        return ns.trim().parse::<u64>().ok().map(|ns| ns / 1000);
+    }
+    let stat = fs::read_to_string(dir.join("cpu.stat")).ok()?;
+    stat.lines()
+        .find_map(|l| l.strip_prefix("usage_usec "))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// 容器累计块设备读写字节：v2 为 io.stat 各设备 rbytes / wbytes 之和，
+/// v1 为 blkio.throttle.io_service_bytes 的 Read / Write 行之和
+pub fn block_io_bytes(pid: i32) -> Option<(u64, u64)> {
+    block_io_bytes_at(Path::new(CGROUP_ROOT), &proc_cgroup(pid)?)
+}
+
+fn block_io_bytes_at(root: &Path, cgroup: &str) -> Option<(u64, u64)> {
+    let dir = controller_dir(root, cgroup, "blkio")?;
+    let (mut read, mut write) = (0, 0);
+    if let Ok(stat) = fs::read_to_string(dir.join("io.stat")) {
+        // "8:0 rbytes=1024 wbytes=2048 rios=1 wios=2 ..."
+        for kv in stat.split_whitespace().filter_map(|t| t.split_once('=')) {
+            match kv {
+                ("rbytes", v) => read += v.parse::<u64>().unwrap_or(0),
+                ("wbytes", v) => write += v.parse::<u64>().unwrap_or(0),
+                _ => {}
+            }
+        }
+        return Some((read, write));
+    }
+    // "8:0 Read 1024" / "8:0 Write 2048" / "Total 3072"
+    let stat = fs::read_to_string(dir.join("blkio.throttle.io_service_bytes")).ok()?;
+    for line in stat.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts.as_slice() {
+            [_, "Read", v] => read += v.parse::<u64>().unwrap_or(0),
+            [_, "Write", v] => write += v.parse::<u64>().unwrap_or(0),
+            _ => {}
+        }
+    }
+    Some((read, write))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    const ID: &str = "3f2a9c0d1e4b";
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// v1 memory 控制器（cgroup_version_at 据此识别 v1）与容器目录
+    fn v1_memory(root: &Path) {
+        write(&root.join("memory/memory.limit_in_bytes"), "9223372036854771712\n");
+        write(&root.join(format!("memory/docker/{}/memory.stat", ID)),
+              "cache 4096\nrss 8192\ntotal_cache 40960\ntotal_rss 81920\n");
+    }
+
+    #[test]
+    fn v2_unified() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        write(&root.join("cgroup.controllers"), "cpu io memory pids\n");
+        let scope = root.join(format!("system.slice/docker-{}.scope", ID));
+        write(&scope.join("memory.stat"), "anon 1048576\nfile 2097152\n");
+        write(&scope.join("cpu.stat"), "usage_usec 123456\nuser_usec 100000\n");
+        let cgroup = format!("0::/system.slice/docker-{}.scope\n", ID);
+
+        let mem = memory_breakdown_at(root, &cgroup).unwrap();
+        assert_eq!((mem.rss, mem.cache), (1048576, 2097152));
+        assert_eq!(cpu_usage_usec_at(root, &cgroup), Some(123456));
+    }
+
+    #[test]
+    fn v1_combined_cpu_cpuacct_mount() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        v1_memory(root);
+        write(&root.join(format!("cpu,cpuacct/docker/{}/cpuacct.usage", ID)), "5000000\n");
+        let cgroup = format!("11:memory:/docker/{id}\n4:cpu,cpuacct:/docker/{id}\n1:name=systemd:/docker/{id}\n", id = ID);
+
+        // v1 优先 total_*（含子 cgroup）
+        let mem = memory_breakdown_at(root, &cgroup).unwrap();
+        assert_eq!((mem.rss, mem.cache), (81920, 40960));
+        // cpuacct.usage 为纳秒
+        assert_eq!(cpu_usage_usec_at(root, &cgroup), Some(5000));
+    }
+
+    #[test]
+    fn v1_single_controller_symlink() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        v1_memory(root);
+        // 合并名目录不存在，只有指向实际挂载点的 cpuacct 符号链接
+        write(&root.join(format!("cpuacct-mount/docker/{}/cpuacct.usage", ID)), "7000\n");
+        symlink(root.join("cpuacct-mount"), root.join("cpuacct")).unwrap();
+        let cgroup = format!("4:cpu,cpuacct:/docker/{}\n", ID);
+
+        assert_eq!(cpu_usage_usec_at(root, &cgroup), Some(7));
+    }
+
+    #[test]
+    fn hybrid_prefers_v1_controllers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        v1_memory(root);
+        write(&root.join(format!("cpu,cpuacct/docker/{}/cpuacct.usage", ID)), "9000000\n");
+        // unified 子目录只有 systemd 的进程跟踪，没有控制器统计
+        write(&root.join("unified/cgroup.controllers"), "");
+        fs::create_dir_all(root.join(format!("unified/docker/{}", ID))).unwrap();
+        let cgroup = format!("11:memory:/docker/{id}\n4:cpu,cpuacct:/docker/{id}\n0::/docker/{id}\n", id = ID);
+
+        assert_eq!(host::cgroup_version_at(root), "v1");
+        assert_eq!(memory_breakdown_at(root, &cgroup).unwrap().rss, 81920);
+        assert_eq!(cpu_usage_usec_at(root, &cgroup), Some(9000));
+    }
+
+    #[test]
+    fn block_io_v2_io_stat() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        write(&root.join("cgroup.controllers"), "cpu io memory pids\n");
+        write(&root.join(format!("system.slice/docker-{}.scope/io.stat", ID)),
+              "8:0 rbytes=1000 wbytes=2000 rios=1 wios=2 dbytes=0 dios=0\n\
+               8:16 rbytes=30 wbytes=40 rios=1 wios=1 dbytes=0 dios=0\n");
+        let cgroup = format!("0::/system.slice/docker-{}.scope\n", ID);
+
+        assert_eq!(block_io_bytes_at(root, &cgroup), Some((1030, 2040)));
+    }
+
+    #[test]
+    fn block_io_v1_throttle() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        v1_memory(root);
+        write(&root.join(format!("blkio/docker/{}/blkio.throttle.io_service_bytes", ID)),
+              "8:0 Read 4096\n8:0 Write 8192\n8:0 Sync 0\n8:0 Total 12288\n\
+               8:16 Read 4\n8:16 Write 8\nTotal 12300\n");
+        let cgroup = format!("9:blkio:/docker/{}\n", ID);
+
+        assert_eq!(block_io_bytes_at(root, &cgroup), Some((4100, 8200)));
+    }
+
+    #[test]
+    fn missing_controller_line() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        v1_memory(root);
+        assert!(cpu_usage_usec_at(root, &format!("11:memory:/docker/{}\n", ID)).is_none());
+    }
+}
@@ -1,49 +1,175 @@
 //! 容器信息收集
 //! 来源：docker inspect / docker stats / docker logs / /proc
 
+use crate::check::cgroup;
 use crate::check::container::*;
-use crate::utils::{Result, SedockerError};
-use std::process::Command;
+use crate::check::filter::ContainerFilter;
+use crate::check::report::SkippedContainer;
+use crate::cli::CheckArgs;
+use crate::utils::{OutputFormat, Result, SedockerError};
+use crate::utils::id::short_id;
+use crate::utils::time::{format_duration, parse_timestamp, seconds_since};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
+/// 容器收集选项
+pub struct CollectOptions {
+    pub verbose: u8,
+    pub log_max_bytes: usize,   // verbose 拉取全部日志时保留的最大字节数
+    pub diff: bool,             // 收集 docker diff
+    pub jobs: usize,            // 并发采集的容器数，1 = 串行
+    pub raw: bool,              // 附带原始 inspect JSON
+    pub sanitize_logs: bool,    // 去掉日志中的 ANSI 转义和控制字符
+}
+
+/// --jobs 默认值上限，避免压垮 dockerd
+const MAX_DEFAULT_JOBS: usize = 8;
+
+impl CollectOptions {
+    pub fn from_args(args: &CheckArgs) -> Self {
+        CollectOptions {
+            verbose: args.verbose,
+            log_max_bytes: args.log_max_bytes,
+            diff: args.diff,
+            raw: args.raw_inspect || args.verbose >= crate::check::VERBOSE_RAW,
+            // 终端输出默认清理；JSON / 归档保留原样，除非显式 --sanitize-logs
+            sanitize_logs: args.sanitize_logs
+                || (args.output == OutputFormat::Text && args.output_dir.is_none()),
+            jobs: args.jobs.unwrap_or_else(|| {
+                std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1)
+                    .min(MAX_DEFAULT_JOBS)
+            }).max(1),
+        }
+    }
+}
 
 // ── 公开接口 ────────────────────────────────────────────────────────────────
 
-pub fn collect_all(verbose: bool) -> Result<Vec<ContainerInfo>> {
+/// 单个容器失败时跳过并记入 `skipped`，不影响其余容器
+pub fn collect_all(
+    opts: &CollectOptions,
+    filter: &ContainerFilter,
+    skipped: &mut Vec<SkippedContainer>,
+) -> Result<Vec<ContainerInfo>> {
     let ids = list_container_ids()?;
-    let mut containers = Vec::new();
 
-    for id in &ids {
-        match collect_one(id, verbose) {
-            Ok(info) => containers.push(info),
-            Err(e)   => eprintln!("warn: skipping {}: {}", id, e),
+    // 工作线程从共享下标取任务，结果按原顺序写回
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<ContainerInfo>>> = Mutex::new(vec![None; ids.len()]);
+    let failed: Mutex<Vec<(usize, SkippedContainer)>> = Mutex::new(Vec::new());
+    let workers = opts.jobs.min(ids.len()).max(1);
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(id) = ids.get(i) else { break };
+                let result = docker_inspect(id).and_then(|json| {
+                    if filter.matches(&json) {
+                        collect_from_inspect(id, &json, opts).map(Some)
+                    } else {
+                        Ok(None)
+                    }
+                });
+                match result {
+                    Ok(info) => results.lock().unwrap()[i] = info,
+                    Err(e)   => {
+                        eprintln!("warn: skipping {}: {}", id, e);
+                        failed.lock().unwrap().push((i, SkippedContainer {
+                            id: id.clone(),
+                            reason: e.to_string(),
+                        }));
+                    }
+                }
+            });
         }
-    }
+    });
+
+    let mut failed = failed.into_inner().unwrap();
+    failed.sort_by_key(|(i, _)| *i);
+    skipped.extend(failed.into_iter().map(|(_, s)| s));
 
-    Ok(containers)
+    Ok(results.into_inner().unwrap().into_iter().flatten().collect())
 }
 
-pub fn collect_one(id: &str, verbose: bool) -> Result<ContainerInfo> {
+pub fn collect_one(id: &str, opts: &CollectOptions) -> Result<ContainerInfo> {
     let json = docker_inspect(id)?;
-    let mut info = parse_inspect(&json, verbose)?;
+    collect_from_inspect(id, &json, opts)
+}
+
+fn collect_from_inspect(id: &str, json: &serde_json::Value, opts: &CollectOptions) -> Result<ContainerInfo> {
+    let mut info = parse_inspect(json)?;
 
     // 仅 running 容器才有 stats
     if info.status == "running" {
         info.resource_usage = fetch_stats(id);
-        // 根据 verbose 模式决定日志行数
-        let log_lines = if verbose { "all" } else { "10" };
-        info.log_tail       = fetch_logs(id, log_lines);
+        if let Some(u) = info.resource_usage.as_mut() {
+            u.memory_breakdown = cgroup::memory_breakdown(info.pid);
+        }
+        // 时钟比对要在容器里 exec date：有副作用（审计日志、进程创建），仅 -v
+        if opts.verbose >= crate::check::VERBOSE_DETAIL {
+            info.clock = fetch_clock(id);
+        }
+    }
+
+    // exited 容器也拿日志，有助于排障；verbose 拉全部日志但按字节上限保留尾部
+    if opts.verbose >= crate::check::VERBOSE_DETAIL {
+        if let Some((lines, truncated)) = fetch_logs_all(id, opts.log_max_bytes) {
+            info.log_tail = Some(lines);
+            info.log_truncated = truncated;
+        }
     } else {
-        // exited 容器也拿日志，有助于排障
-        let log_lines = if verbose { "all" } else { "10" };
-        info.log_tail = fetch_logs(id, log_lines);
+        info.log_tail = fetch_logs(id, "10");
+    }
+    if opts.sanitize_logs {
+        if let Some(lines) = info.log_tail.as_mut() {
+            for line in lines.iter_mut() {
+                *line = sanitize_log_line(line);
+            }
+        }
+    }
+
+    if opts.diff {
+        info.fs_changes = fetch_diff(id);
+    }
+
+    if opts.raw {
+        info.raw = Some(json.clone());
     }
 
     Ok(info)
 }
 
+/// 容器内 `date +%s %z` 与 exec 前后宿主机时间中点比较；镜像无 date 时为 None
+fn fetch_clock(id: &str) -> Option<ContainerClock> {
+    let before = chrono::Utc::now();
+    let out = Command::new("docker")
+        .args(["exec", id, "date", "+%s %z"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let after = chrono::Utc::now();
+    if !out.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut parts = text.split_whitespace();
+    let container_secs: i64 = parts.next()?.parse().ok()?;
+    let utc_offset = parts.next().unwrap_or("").to_string();
+    let host_mid_ms = (before.timestamp_millis() + after.timestamp_millis()) / 2;
+    // %s 截断到整秒，补半秒取区间中点
+    let skew_seconds = ((container_secs * 1000 + 500 - host_mid_ms) as f64 / 1000.0).round() as i64;
+    Some(ContainerClock { skew_seconds, utc_offset })
+}
+
 // ── docker ps / inspect ─────────────────────────────────────────────────────
 
-fn list_container_ids() -> Result<Vec<String>> {
+pub fn list_container_ids() -> Result<Vec<String>> {
     let out = Command::new("docker")
         .args(&["ps", "-a", "--format", "{{.ID}}"])
         .output()
@@ -63,7 +189,7 @@ fn list_container_ids() -> Result<Vec<String>> {
         .collect())
 }
 
-fn docker_inspect(id: &str) -> Result<serde_json::Value> {
+pub fn docker_inspect(id: &str) -> Result<serde_json::Value> {
     let out = Command::new("docker")
         .args(&["inspect", id])
         .output()
@@ -84,8 +210,8 @@ fn docker_inspect(id: &str) -> Result<serde_json::Value> {
 
 // ── inspect パーサー ─────────────────────────────────────────────────────────
 
-fn parse_inspect(c: &serde_json::Value, _verbose: bool) -> Result<ContainerInfo> {
-    let id: String = c["Id"].as_str().unwrap_or("").chars().take(12).collect();
+fn parse_inspect(c: &serde_json::Value) -> Result<ContainerInfo> {
+    let id = short_id(c["Id"].as_str().unwrap_or(""));
     let name = c["Name"].as_str().unwrap_or("")
         .trim_start_matches('/').to_string();
     let image    = str_val(c, &["Config", "Image"]);
@@ -114,6 +240,11 @@ fn parse_inspect(c: &serde_json::Value, _verbose: bool) -> Result<ContainerInfo>
         .unwrap_or_default();
     let working_dir = str_val(c, &["Config", "WorkingDir"]);
     let user = str_val(c, &["Config", "User"]);
+    let labels = c["Config"]["Labels"].as_object()
+        .map(|obj| obj.iter()
+            .map(|(k, v)| (k.clone(), v.as_str().unwrap_or("").to_string()))
+            .collect())
+        .unwrap_or_default();
 
     let status      = str_val(c, &["State", "Status"]);
     let exit_code   = c["State"]["ExitCode"].as_i64().unwrap_or(0);
@@ -124,6 +255,7 @@ fn parse_inspect(c: &serde_json::Value, _verbose: bool) -> Result<ContainerInfo>
 
     let restart_policy = str_val(c, &["HostConfig", "RestartPolicy", "Name"]);
     let restart_count  = c["RestartCount"].as_i64().unwrap_or(0);
+    let auto_remove    = c["HostConfig"]["AutoRemove"].as_bool().unwrap_or(false);
 
     let env = c["Config"]["Env"].as_array()
         .map(|a| a.iter()
@@ -133,32 +265,118 @@ fn parse_inspect(c: &serde_json::Value, _verbose: bool) -> Result<ContainerInfo>
         .unwrap_or_default();
 
     let ports        = parse_ports(c);
+    let exposed_ports = parse_exposed_ports(c);
     let networks     = parse_networks(c);
     let network_mode = str_val(c, &["HostConfig", "NetworkMode"]);
     let mounts       = parse_mounts(c);
     let resource_config = parse_resource_config(c);
-    let security_config = parse_security_config(c);
-    let processes = parse_process_info(c).unwrap_or_default();
+    let mut security_config = parse_security_config(c);
+    let main_pid = c["State"]["Pid"].as_i64().unwrap_or(0) as i32;
+    (security_config.effective_apparmor, security_config.effective_selinux) = read_mac_labels(main_pid);
+
+    let since = |ts: &str| seconds_since(ts).map(|s| s as u64);
+    let age_seconds = since(&created);
+    let (uptime_seconds, finished_ago_seconds) = if status == "running" {
+        (since(&started_at), None)
+    } else {
+        (None, since(&finished_at))
+    };
+    let status_age = match (uptime_seconds, finished_ago_seconds) {
+        (Some(up), _)  => format!("up {}", format_duration(up)),
+        (_, Some(ago)) => format!("{} ago", format_duration(ago)),
+        _              => String::new(),
+    };
+    let lifecycle = parse_lifecycle(c);
+    let oom_score     = read_proc_i32(main_pid, "oom_score");
+    let oom_score_adj = read_proc_i32(main_pid, "oom_score_adj");
+
+    // 账户表每个容器只取一次，进程用户名和 Users/Groups 都在本地解析
+    let accounts = ContainerAccounts::fetch(&id, main_pid);
+    let processes = parse_process_info(c, &accounts).unwrap_or_default();
 
     // Collect users and groups from container (always, for normal mode display)
-    let users_groups = collect_users_groups(id.as_str()).unwrap_or_default();
+    let users_groups = collect_users_groups(&accounts);
 
     Ok(ContainerInfo {
         id, name, image, image_id,
         status, exit_code, oom_killed,
-        created, started_at, finished_at,
-        restart_policy, restart_count, env,
-        cmd, entrypoint, path, args, working_dir, user,
+        created, started_at, finished_at, pid: main_pid,
+        age_seconds, image_age_seconds: None, uptime_seconds, finished_ago_seconds, status_age,
+        lifecycle,
+        timeline: Vec::new(),
+        last_death: None,
+        healthcheck_config: parse_healthcheck(c),
+        restart_policy, restart_count, auto_remove, env,
+        env_source: BTreeMap::new(),
+        cmd, entrypoint, path, args, working_dir, user, labels,
         security: security_config,
-        ports, networks, network_mode, mounts,
+        ports, exposed_ports, networks, network_mode, mounts,
         resource_config,
         resource_usage: None,
+        oom_score, oom_score_adj,
+        clock: None,
         log_tail: None,
+        log_truncated: false,
+        log_findings: Vec::new(),
+        fs_changes: Vec::new(),
         processes,
         users_groups,
+        raw: None,
+    })
+}
+
+/// docker 健康检查默认值（秒 / 次）
+const HEALTHCHECK_DEFAULT_INTERVAL: u64 = 30;
+const HEALTHCHECK_DEFAULT_TIMEOUT: u64 = 30;
+const HEALTHCHECK_DEFAULT_RETRIES: u64 = 3;
+
+/// Config.Healthcheck；时长单位为纳秒，0 / 缺省取 docker 默认值
+fn parse_healthcheck(c: &serde_json::Value) -> Option<HealthcheckConfig> {
+    let hc = &c["Config"]["Healthcheck"];
+    let test: Vec<String> = hc["Test"].as_array()?
+        .iter()
+        .filter_map(|v| v.as_str().map(String::from))
+        .collect();
+    if test.is_empty() || test[0] == "NONE" {
+        return None;
+    }
+    let secs = |key: &str, default: u64| match hc[key].as_u64() {
+        Some(ns) if ns > 0 => ns / 1_000_000_000,
+        _ => default,
+    };
+    Some(HealthcheckConfig {
+        interval_secs: secs("Interval", HEALTHCHECK_DEFAULT_INTERVAL),
+        timeout_secs: secs("Timeout", HEALTHCHECK_DEFAULT_TIMEOUT),
+        retries: match hc["Retries"].as_u64() {
+            Some(n) if n > 0 => n,
+            _ => HEALTHCHECK_DEFAULT_RETRIES,
+        },
+        start_period_secs: secs("StartPeriod", 0),
+        test,
     })
 }
 
+fn parse_lifecycle(c: &serde_json::Value) -> Lifecycle {
+    let ts = |v: &serde_json::Value| v.as_str().and_then(parse_timestamp);
+    let health = &c["State"]["Health"];
+    let health_checks = health["Log"].as_array()
+        .map(|arr| arr.iter().map(|h| HealthCheck {
+            start: ts(&h["Start"]),
+            end: ts(&h["End"]),
+            exit_code: h["ExitCode"].as_i64().unwrap_or(0),
+            output: h["Output"].as_str().unwrap_or("").trim().to_string(),
+        }).collect())
+        .unwrap_or_default();
+
+    Lifecycle {
+        created: ts(&c["Created"]),
+        started: ts(&c["State"]["StartedAt"]),
+        finished: ts(&c["State"]["FinishedAt"]),
+        health_status: health["Status"].as_str().map(String::from),
+        health_checks,
+    }
+}
+
 fn parse_ports(c: &serde_json::Value) -> Vec<PortMapping> {
     let mut ports = Vec::new();
     if let Some(bindings) = c["HostConfig"]["PortBindings"].as_object() {
@@ -183,6 +401,20 @@ fn parse_ports(c: &serde_json::Value) -> Vec<PortMapping> {
     ports
 }
 
+/// Config.ExposedPorts 的键（"80/tcp"），按端口号排序
+fn parse_exposed_ports(c: &serde_json::Value) -> Vec<String> {
+    let mut ports: Vec<String> = c["Config"]["ExposedPorts"].as_object()
+        .map(|o| o.keys().cloned().collect())
+        .unwrap_or_default();
+    ports.sort_by_key(|p| (port_number(p), p.clone()));
+    ports
+}
+
+/// "5432/tcp" → 5432
+pub fn port_number(spec: &str) -> u16 {
+    spec.split('/').next().and_then(|p| p.parse().ok()).unwrap_or(0)
+}
+
 fn parse_networks(c: &serde_json::Value) -> Vec<NetworkEntry> {
     let mut result = Vec::new();
     if let Some(networks) = c["NetworkSettings"]["Networks"].as_object() {
@@ -199,10 +431,11 @@ fn parse_networks(c: &serde_json::Value) -> Vec<NetworkEntry> {
 }
 
 fn parse_mounts(c: &serde_json::Value) -> Vec<MountInfo> {
-    c["Mounts"].as_array()
+    let mut mounts: Vec<MountInfo> = c["Mounts"].as_array()
         .map(|arr| arr.iter().map(|m| {
             let source = m["Source"].as_str().unwrap_or("").to_string();
-            let permissions = if !source.is_empty() && std::path::Path::new(&source).exists() {
+            let source_type = source_type(&source);
+            let permissions = if source_type == "dir" || source_type == "file" {
                 collect_path_permissions(&source)
             } else {
                 vec![]
@@ -215,38 +448,118 @@ fn parse_mounts(c: &serde_json::Value) -> Vec<MountInfo> {
                 mode:        m["Mode"].as_str().unwrap_or("").to_string(),
                 rw:          m["RW"].as_bool().unwrap_or(false),
                 permissions,
+                source_type,
+                tmpfs:       None,
             }
         }).collect())
-        .unwrap_or_default()
+        .unwrap_or_default();
+
+    // --tmpfs 挂载不出现在 Mounts 中，单独来自 HostConfig.Tmpfs（dest → "rw,noexec,size=64m"）
+    if let Some(map) = c["HostConfig"]["Tmpfs"].as_object() {
+        for (dest, opts) in map {
+            if mounts.iter().any(|m| &m.destination == dest) {
+                continue;
+            }
+            let opts = opts.as_str().unwrap_or("");
+            let (tmpfs, rw) = parse_tmpfs_options(opts);
+            mounts.push(MountInfo {
+                mount_type:  "tmpfs".to_string(),
+                source:      String::new(),
+                destination: dest.clone(),
+                mode:        opts.to_string(),
+                rw,
+                permissions: vec![],
+                source_type: String::new(),
+                tmpfs:       Some(tmpfs),
+            });
+        }
+    }
+    mounts
 }
 
+/// 宿主机侧挂载源的类型（跟随符号链接）
+fn source_type(source: &str) -> String {
+    if source.is_empty() {
+        return String::new();
+    }
+    match std::fs::metadata(source) {
+        Ok(m) if m.is_dir()  => "dir",
+        Ok(m) if m.is_file() => "file",
+        Ok(_)                => "other",
+        Err(_)               => "missing",
+    }.to_string()
+}
+
+/// 逐项解析，后出现的覆盖先出现的（与 Docker 合并默认选项的方式一致）
+fn parse_tmpfs_options(opts: &str) -> (TmpfsOptions, bool) {
+    let mut t = TmpfsOptions { size_bytes: None, exec: false, suid: false };
+    let mut rw = true;
+    for o in opts.split(',').map(str::trim) {
+        match o {
+            "exec"   => t.exec = true,
+            "noexec" => t.exec = false,
+            "suid"   => t.suid = true,
+            "nosuid" => t.suid = false,
+            "rw"     => rw = true,
+            "ro"     => rw = false,
+            _ => if let Some(v) = o.strip_prefix("size=") {
+                t.size_bytes = parse_size(v);
+            },
+        }
+    }
+    (t, rw)
+}
+
+/// "65536k" / "64m" / "1g" / 纯字节数；百分比等无法换算的返回 None
+fn parse_size(v: &str) -> Option<u64> {
+    let v = v.to_ascii_lowercase();
+    let (num, mult) = match v.chars().last()? {
+        'k' => (&v[..v.len() - 1], 1u64 << 10),
+        'm' => (&v[..v.len() - 1], 1 << 20),
+        'g' => (&v[..v.len() - 1], 1 << 30),
+        _   => (v.as_str(), 1),
+    };
+    num.parse::<u64>().ok().map(|n| n * mult)
+}
+
+/// 遍历挂载源下所有文件的 uid/gid/mode
+///
+/// 使用 symlink_metadata：符号链接只记录、不跟随（避免 -> / 扫描整个宿主机）。
+/// 按 (dev, inode) 去重：硬链接或经多条路径可达的目录只记录、展开一次，同时打破环路。
+/// 挂载源本身仍跟随一次。
 fn collect_path_permissions(path: &str) -> Vec<crate::check::container::PathPermission> {
+    use std::collections::HashSet;
     use std::os::unix::fs::MetadataExt;
     use std::fs;
+    use std::path::PathBuf;
     
     let mut permissions = Vec::new();
-    
+    let mut visited: HashSet<(u64, u64)> = HashSet::new();
+    let mut stack: Vec<(PathBuf, fs::Metadata)> = Vec::new();
+
     if let Ok(metadata) = fs::metadata(path) {
+        stack.push((PathBuf::from(path), metadata));
+    }
+
+    while let Some((p, metadata)) = stack.pop() {
+        if !visited.insert((metadata.dev(), metadata.ino())) {
+            continue;
+        }
         permissions.push(crate::check::container::PathPermission {
-            path: path.to_string(),
+            path: p.to_string_lossy().to_string(),
             uid: metadata.uid(),
             gid: metadata.gid(),
             mode: metadata.mode(),
         });
-    }
-    
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries.flatten() {
-            if let Ok(metadata) = entry.metadata() {
-                permissions.push(crate::check::container::PathPermission {
-                    path: entry.path().to_string_lossy().to_string(),
-                    uid: metadata.uid(),
-                    gid: metadata.gid(),
-                    mode: metadata.mode(),
-                });
-                
-                if metadata.is_dir() {
-                    permissions.extend(collect_path_permissions(&entry.path().to_string_lossy()));
+
+        if !metadata.is_dir() {
+            continue;
+        }
+
+        if let Ok(entries) = fs::read_dir(&p) {
+            for entry in entries.flatten() {
+                if let Ok(m) = fs::symlink_metadata(entry.path()) {
+                    stack.push((entry.path(), m));
                 }
             }
         }
@@ -264,60 +577,32 @@ fn parse_resource_config(c: &serde_json::Value) -> ResourceConfig {
         memory_limit: hc["Memory"].as_u64().unwrap_or(0),
         memory_swap:  hc["MemorySwap"].as_i64().unwrap_or(0),
         pids_limit:   hc["PidsLimit"].as_i64().unwrap_or(0),
+        oom_kill_disable: hc["OomKillDisable"].as_bool().unwrap_or(false),
     }
 }
 
-fn parse_process_info(c: &serde_json::Value) -> Option<Vec<ProcessInfo>> {
+fn parse_process_info(c: &serde_json::Value, accounts: &ContainerAccounts) -> Option<Vec<ProcessInfo>> {
     let host_pid = c["State"]["Pid"].as_i64()? as i32;
     if host_pid <= 0 { return None; }
 
     // Get container ID from inspect JSON
     let container_id = c["Id"].as_str()?;
-    let short_id = container_id.chars().take(12).collect::<String>();
-    
-    // Use docker top to get all processes in the container
-    let mut processes = collect_container_processes(&short_id)?;
-    
-    // Try to identify the main process (PID 1 in container)
-    // We can check if any process has PPID = 0 (orphaned) or is the entrypoint/cmd
-    if let Some(main_pid) = get_container_main_pid(&short_id, host_pid) {
-        for process in &mut processes {
-            if process.pid == main_pid {
-                // Mark this as the main process
-                // We'll add a flag or special handling in display
-            }
-        }
-    }
-    
-    Some(processes)
+    // Prefer docker top; fall back to scanning /proc (paused containers, runtimes without top)
+    collect_container_processes(&short_id(container_id), accounts)
+        .or_else(|| collect_processes_from_proc(container_id, accounts))
 }
 
-fn get_container_main_pid(_container_id: &str, host_pid: i32) -> Option<i32> {
-    // The main container process is the one with PID 1 in the container namespace
-    // We can try to get this from /proc/<host_pid>/status which shows NSpid
-    let status_path = format!("/proc/{}/status", host_pid);
-    if let Ok(content) = std::fs::read_to_string(&status_path) {
-        for line in content.lines() {
-            if line.starts_with("NSpid:") {
-                // NSpid shows PID in different namespaces
-                // Format: NSpid:  <host_pid>    <container_pid> ...
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 3 {
-                    if let Ok(container_ns_pid) = parts[2].parse::<i32>() {
-                        if container_ns_pid == 1 {
-                            return Some(host_pid);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    // Fallback: the process with PPID = 0 (init process) is often the main one
-    None
+/// 进程在最内层 PID 命名空间中的 PID
+/// NSpid 格式: "NSpid:  <host_pid> <container_pid>"（嵌套命名空间时更多列，末项最内层）
+fn read_ns_pid(host_pid: i32) -> Option<i32> {
+    read_status_field(host_pid, "NSpid:")?
+        .split_whitespace()
+        .last()?
+        .parse()
+        .ok()
 }
 
-fn collect_container_processes(container_id: &str) -> Option<Vec<ProcessInfo>> {
+fn collect_container_processes(container_id: &str, accounts: &ContainerAccounts) -> Option<Vec<ProcessInfo>> {
     use std::process::Command;
     
     // Run docker top to get PIDs and commands
@@ -352,68 +637,64 @@ fn collect_container_processes(container_id: &str) -> Option<Vec<ProcessInfo>> {
         // cmd might contain spaces, so join remaining parts
         let cmd = parts[2..].join(" ");
         
-        // Get uid/gid from /proc
-        let (uid, gid) = get_process_uid_gid(pid);
-        
-        // Get user and group names from container filesystem
-        let (user, group) = get_container_user_group(container_id, uid, gid);
-        
-        // Try to get executable path from /proc
-        let exe_path = get_process_exe_path(pid);
-        let cwd = get_process_cwd(pid);
-        
-        processes.push(ProcessInfo {
-            pid,
-            ppid,
-            uid,
-            gid,
-            user,
-            group,
-            cmd,
-            exe_path,
-            cwd,
-        });
+        processes.push(build_process_info(pid, ppid, cmd, accounts));
     }
     
     Some(processes)
 }
 
-fn get_container_user_group(container_id: &str, uid: u32, gid: u32) -> (String, String) {
-    use std::process::Command;
-    
-    // Try to get user name from container's /etc/passwd
-    let user_output = Command::new("docker")
-        .args(&["exec", container_id, "getent", "passwd", &uid.to_string()])
-        .output();
-    
-    let user = match user_output {
-        Ok(output) if output.status.success() => {
-            String::from_utf8_lossy(&output.stdout)
-                .split(':')
-                .nth(0)
-                .unwrap_or(&uid.to_string())
-                .to_string()
-        }
-        _ => uid.to_string(),
-    };
-    
-    // Try to get group name from container's /etc/group
-    let group_output = Command::new("docker")
-        .args(&["exec", container_id, "getent", "group", &gid.to_string()])
-        .output();
-    
-    let group = match group_output {
-        Ok(output) if output.status.success() => {
-            String::from_utf8_lossy(&output.stdout)
-                .split(':')
-                .nth(0)
-                .unwrap_or(&gid.to_string())
-                .to_string()
-        }
-        _ => gid.to_string(),
-    };
-    
-    (user, group)
+/// docker top 不可用时的回退：按 cgroup 找到容器进程，直接读 /proc
+fn collect_processes_from_proc(container_id: &str, accounts: &ContainerAccounts) -> Option<Vec<ProcessInfo>> {
+    let pids = cgroup::container_pids(container_id);
+    if pids.is_empty() {
+        return None;
+    }
+
+    Some(pids.into_iter().map(|pid| {
+        let ppid = read_status_field(pid, "PPid:")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        build_process_info(pid, ppid, read_cmdline(pid), accounts)
+    }).collect())
+}
+
+/// 补齐 uid/gid、用户名/组名、exe 和 cwd
+fn build_process_info(pid: i32, ppid: i32, cmd: String, accounts: &ContainerAccounts) -> ProcessInfo {
+    let (uid, gid) = get_process_uid_gid(pid);
+    ProcessInfo {
+        pid,
+        container_pid: read_ns_pid(pid),
+        ppid,
+        uid,
+        gid,
+        user: accounts.user_name(uid),
+        group: accounts.group_name(gid),
+        cmd,
+        exe_path: get_process_exe_path(pid),
+        cwd: get_process_cwd(pid),
+    }
+}
+
+fn read_status_field(pid: i32, key: &str) -> Option<String> {
+    let content = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    content.lines()
+        .find_map(|l| l.strip_prefix(key))
+        .map(|v| v.trim().to_string())
+}
+
+/// /proc/<pid>/cmdline 以 NUL 分隔；内核线程为空时用 [comm] 表示，与 ps 一致
+fn read_cmdline(pid: i32) -> String {
+    let raw = std::fs::read(format!("/proc/{}/cmdline", pid)).unwrap_or_default();
+    let cmd = raw.split(|b| *b == 0)
+        .filter(|a| !a.is_empty())
+        .map(|a| String::from_utf8_lossy(a).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !cmd.is_empty() {
+        return cmd;
+    }
+    let comm = std::fs::read_to_string(format!("/proc/{}/comm", pid)).unwrap_or_default();
+    format!("[{}]", comm.trim())
 }
 
 fn get_process_uid_gid(pid: i32) -> (u32, u32) {
@@ -444,6 +725,16 @@ fn get_process_uid_gid(pid: i32) -> (u32, u32) {
     (0, 0)
 }
 
+/// 读取 /proc/<pid>/<name> 中的单个整数（如 oom_score）
+fn read_proc_i32(pid: i32, name: &str) -> Option<i32> {
+    if pid <= 0 {
+        return None;
+    }
+    std::fs::read_to_string(format!("/proc/{}/{}", pid, name))
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+}
+
 fn get_process_exe_path(pid: i32) -> Option<String> {
     if pid <= 0 {
         return None;
@@ -472,7 +763,7 @@ fn get_process_cwd(pid: i32) -> Option<String> {
 
 fn fetch_stats(id: &str) -> Option<ResourceUsage> {
     let out = Command::new("docker")
-        .args(&[
+        .args([
             "stats", "--no-stream",
             "--format", "{{json .}}",
             id,
@@ -483,7 +774,30 @@ fn fetch_stats(id: &str) -> Option<ResourceUsage> {
     if !out.status.success() { return None; }
 
     let j: serde_json::Value = serde_json::from_slice(&out.stdout).ok()?;
+    Some(parse_stats_json(&j))
+}
+
+/// 一次 docker stats 获取所有运行中容器的快照：短 ID → 用量
+pub fn fetch_stats_all() -> std::collections::HashMap<String, ResourceUsage> {
+    let out = match Command::new("docker")
+        .args(["stats", "--no-stream", "--no-trunc", "--format", "{{json .}}"])
+        .output()
+    {
+        Ok(o) if o.status.success() => o,
+        _ => return Default::default(),
+    };
 
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .map(|j| {
+            let id = short_id(j["ID"].as_str().unwrap_or(""));
+            (id, parse_stats_json(&j))
+        })
+        .collect()
+}
+
+fn parse_stats_json(j: &serde_json::Value) -> ResourceUsage {
     // docker stats json 格式：字段值为字符串，如 "1.5GiB / 3.8GiB"
     let memory_usage  = parse_stat_mem(j["MemUsage"].as_str().unwrap_or(""));
     let cpu_percent   = parse_stat_pct(j["CPUPerc"].as_str().unwrap_or(""));
@@ -494,7 +808,7 @@ fn fetch_stats(id: &str) -> Option<ResourceUsage> {
         .and_then(|s| s.parse().ok())
         .unwrap_or(0);
 
-    Some(ResourceUsage {
+    ResourceUsage {
         cpu_percent,
         memory_usage: memory_usage.0,
         memory_limit: memory_usage.1,
@@ -504,7 +818,152 @@ fn fetch_stats(id: &str) -> Option<ResourceUsage> {
         net_rx,
         net_tx,
         pids,
-    })
+        memory_breakdown: None,
+        sample_secs: None,
+        net_rx_rate: None,
+        net_tx_rate: None,
+        block_read_rate: None,
+        block_write_rate: None,
+    }
+}
+
+/// 一次 docker image inspect 取所有用到的镜像的 Created 和 Config.Env，
+/// 填充 image_age_seconds 与 env_source
+pub fn attach_image_info(containers: &mut [ContainerInfo]) {
+    let mut ids: Vec<&str> = containers.iter().map(|c| c.image_id.as_str()).filter(|i| !i.is_empty()).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.is_empty() {
+        return;
+    }
+    let output = match Command::new("docker")
+        .args(["image", "inspect", "--format", "{{.Id}} {{.Created}} {{json .Config.Env}}"])
+        .args(&ids)
+        .output()
+    {
+        Ok(o) => o,
+        Err(_) => return,
+    };
+    // 部分镜像已删除时退出码非零，但其余镜像仍有输出
+    let mut created: HashMap<String, u64> = HashMap::new();
+    let mut image_env: HashMap<String, HashMap<String, String>> = HashMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut parts = line.splitn(3, ' ');
+        let (Some(id), Some(ts)) = (parts.next(), parts.next()) else { continue };
+        if let Some(age) = seconds_since(ts) {
+            created.insert(id.to_string(), age as u64);
+        }
+        let env: Vec<String> = parts.next()
+            .and_then(|j| serde_json::from_str::<Option<Vec<String>>>(j).ok().flatten())
+            .unwrap_or_default();
+        image_env.insert(id.to_string(), env.iter()
+            .filter_map(|e| e.split_once('='))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect());
+    }
+    for c in containers.iter_mut() {
+        c.image_age_seconds = created.get(&c.image_id).copied();
+        if let Some(base) = image_env.get(&c.image_id) {
+            c.env_source = classify_env(&c.env, base);
+        }
+    }
+}
+
+/// 与镜像 Config.Env 对比：同名同值为 image，同名异值为 overridden，镜像中没有为 added
+fn classify_env(env: &[String], image: &HashMap<String, String>) -> BTreeMap<String, EnvSource> {
+    env.iter()
+        .filter_map(|e| e.split_once('='))
+        .map(|(k, v)| {
+            let source = match image.get(k) {
+                Some(iv) if iv == v => EnvSource::Image,
+                Some(_) => EnvSource::Overridden,
+                None => EnvSource::Added,
+            };
+            (k.to_string(), source)
+        })
+        .collect()
+}
+
+/// --sample 使用的精确累计计数器
+struct SampleCounters {
+    cpu_usec: Option<u64>,
+    net: Option<(u64, u64)>,     // rx / tx 字节
+    block: Option<(u64, u64)>,   // read / write 字节
+}
+
+impl SampleCounters {
+    fn read(pid: i32) -> Self {
+        SampleCounters {
+            cpu_usec: cgroup::cpu_usage_usec(pid),
+            net: net_dev_bytes(pid),
+            block: cgroup::block_io_bytes(pid),
+        }
+    }
+}
+
+/// --sample：间隔 secs 秒对所有运行中容器取两次快照，计算网络/块设备 IO 速率。
+/// 速率来自精确字节计数器（/proc/<pid>/net/dev、cgroup io.stat / blkio），
+/// 而不是 docker stats 的三位有效数字显示值；读不到的计数器速率为 None。
+/// CPU% 优先用 cgroup 累计 CPU 时间差（100% = 1 核），否则取两次 docker stats 的均值
+pub fn sample_rates(containers: &mut [ContainerInfo], secs: u64) {
+    let secs = secs.max(1);
+    let first = fetch_stats_all();
+
+    // 计数器紧贴 sleep 两侧读取，按实际间隔计算，不含 docker stats 自身耗时
+    let before: Vec<SampleCounters> = containers.iter().map(|c| SampleCounters::read(c.pid)).collect();
+    let started = std::time::Instant::now();
+    std::thread::sleep(std::time::Duration::from_secs(secs));
+    let after: Vec<SampleCounters> = containers.iter().map(|c| SampleCounters::read(c.pid)).collect();
+    let elapsed = started.elapsed().as_secs_f64();
+
+    let second = fetch_stats_all();
+    let rate = |a: u64, b: u64| b.saturating_sub(a) as f64 / elapsed;
+    let pair_rate = |a: Option<(u64, u64)>, b: Option<(u64, u64)>| match (a, b) {
+        (Some(a), Some(b)) => (Some(rate(a.0, b.0)), Some(rate(a.1, b.1))),
+        _ => (None, None),
+    };
+
+    for ((c, before), after) in containers.iter_mut().zip(before).zip(after) {
+        let (a, b) = match (first.get(&c.id), second.get(&c.id)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => continue,
+        };
+        let mut usage = b.clone();
+        usage.sample_secs = Some(secs);
+        (usage.net_rx_rate, usage.net_tx_rate) = pair_rate(before.net, after.net);
+        (usage.block_read_rate, usage.block_write_rate) = pair_rate(before.block, after.block);
+        usage.cpu_percent = match (before.cpu_usec, after.cpu_usec) {
+            (Some(x), Some(y)) => y.saturating_sub(x) as f64 / (elapsed * 1_000_000.0) * 100.0,
+            _ => (a.cpu_percent + b.cpu_percent) / 2.0,
+        };
+        c.resource_usage = Some(usage);
+    }
+}
+
+/// 容器网络命名空间内除 lo 外所有接口的累计收发字节（/proc/<pid>/net/dev）
+fn net_dev_bytes(pid: i32) -> Option<(u64, u64)> {
+    if pid <= 0 {
+        return None;
+    }
+    parse_net_dev(&std::fs::read_to_string(format!("/proc/{}/net/dev", pid)).ok()?)
+}
+
+/// net/dev 格式：两行表头，之后 "iface: rx_bytes rx_packets ... (8 列) tx_bytes ..."
+fn parse_net_dev(content: &str) -> Option<(u64, u64)> {
+    let mut total = None;
+    for line in content.lines().skip(2) {
+        let Some((iface, counters)) = line.split_once(':') else { continue };
+        if iface.trim() == "lo" {
+            continue;
+        }
+        let fields: Vec<u64> = counters.split_whitespace().filter_map(|v| v.parse().ok()).collect();
+        if fields.len() < 9 {
+            continue;
+        }
+        let (rx, tx) = total.unwrap_or((0, 0));
+        total = Some((rx + fields[0], tx + fields[8]));
+    }
+    total
 }
 
 /// 解析 "1.5GiB / 3.8GiB" → (used_bytes, limit_bytes)
@@ -548,17 +1007,10 @@ fn parse_stat_pair(s: &str) -> (u64, u64) {
 // ── docker logs ─────────────────────────────────────────────────────────────
 
 fn fetch_logs(id: &str, tail: &str) -> Option<Vec<String>> {
-    let out = if tail == "all" {
-        Command::new("docker")
-            .args(&["logs", "--timestamps", id])
-            .output()
-            .ok()?
-    } else {
-        Command::new("docker")
-            .args(&["logs", "--tail", tail, "--timestamps", id])
-            .output()
-            .ok()?
-    };
+    let out = Command::new("docker")
+        .args(["logs", "--tail", tail, "--timestamps", id])
+        .output()
+        .ok()?;
 
     // docker logs 写 stderr
     let combined = [out.stdout.as_slice(), out.stderr.as_slice()].concat();
@@ -567,6 +1019,132 @@ fn fetch_logs(id: &str, tail: &str) -> Option<Vec<String>> {
     Some(s.lines().map(String::from).collect())
 }
 
+/// 去掉 ANSI 转义序列（CSI / OSC / 其他 ESC 双字节序列）和除 \t 外的控制字符
+fn sanitize_log_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            match chars.next() {
+                // CSI：ESC [ 参数... 终止字节 0x40–0x7E
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC：ESC ] ... 以 BEL 或 ESC \ 结束
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' {
+                            break;
+                        }
+                        if c == '\x1b' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if c == '\t' || !c.is_control() {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// 流式读取全部日志，只保留最后 max_bytes 字节内的行，不在内存中物化完整日志；
+/// 返回 (行, 是否丢弃过更早的日志)
+fn fetch_logs_all(id: &str, max_bytes: usize) -> Option<(Vec<String>, bool)> {
+    let mut child = Command::new("docker")
+        .args(["logs", "--timestamps", id])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    // stdout / stderr 须同时读取，否则一侧管道写满会阻塞 docker
+    let stderr = child.stderr.take()?;
+    let err_reader = std::thread::spawn(move || tail_lines(stderr, max_bytes));
+    let (mut lines, mut truncated) = tail_lines(child.stdout.take()?, max_bytes);
+    let (err_lines, err_truncated) = err_reader.join().unwrap_or_default();
+    let _ = child.wait();
+
+    // 两路都带 --timestamps 前缀，按时间合并后再整体裁剪
+    lines.extend(err_lines);
+    let mut lines: Vec<String> = lines.into_iter().collect();
+    lines.sort_by(|a, b| log_timestamp(a).cmp(log_timestamp(b)));
+
+    let mut total: usize = lines.iter().map(|l| l.len() + 1).sum();
+    let mut skip = 0;
+    while total > max_bytes && skip < lines.len() {
+        total -= lines[skip].len() + 1;
+        skip += 1;
+    }
+    truncated |= err_truncated || skip > 0;
+
+    Some((lines.into_iter().skip(skip).collect(), truncated))
+}
+
+/// 环形缓冲读取：保留尾部不超过 max_bytes 的行，返回 (行, 是否丢弃过)
+fn tail_lines<R: Read>(reader: R, max_bytes: usize) -> (VecDeque<String>, bool) {
+    let mut ring = VecDeque::new();
+    let mut size = 0usize;
+    let mut dropped = false;
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let line = String::from_utf8_lossy(&buf).trim_end_matches(['\n', '\r']).to_string();
+        size += line.len() + 1;
+        ring.push_back(line);
+        while size > max_bytes {
+            match ring.pop_front() {
+                Some(old) => { size -= old.len() + 1; dropped = true; }
+                None => break,
+            }
+        }
+    }
+    (ring, dropped)
+}
+
+fn log_timestamp(line: &str) -> &str {
+    line.split_once(' ').map(|(ts, _)| ts).unwrap_or("")
+}
+
+// ── docker diff ─────────────────────────────────────────────────────────────
+
+/// 解析 docker diff 输出："A /path" / "C /path" / "D /path"
+fn fetch_diff(id: &str) -> Vec<FsChange> {
+    let out = match Command::new("docker").args(["diff", id]).output() {
+        Ok(o) if o.status.success() => o,
+        _ => return Vec::new(),
+    };
+
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (kind, path) = line.split_once(' ')?;
+            let kind = match kind {
+                "A" => FsChangeKind::Added,
+                "C" => FsChangeKind::Changed,
+                "D" => FsChangeKind::Deleted,
+                _   => return None,
+            };
+            Some(FsChange { kind, path: path.to_string() })
+        })
+        .collect()
+}
+
 // ── 安全配置解析 ─────────────────────────────────────────────────────────────
 
 fn parse_security_config(c: &serde_json::Value) -> SecurityConfig {
@@ -579,6 +1157,12 @@ fn parse_security_config(c: &serde_json::Value) -> SecurityConfig {
             .map(|s| s.to_string())
             .collect())
         .unwrap_or_default();
+    let cap_drop = hc["CapDrop"].as_array()
+        .map(|arr| arr.iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.to_string())
+            .collect())
+        .unwrap_or_default();
     
     // 解析 seccomp 和 apparmor 配置
     let seccomp_profile = hc["SecurityOpt"].as_array()
@@ -589,6 +1173,11 @@ fn parse_security_config(c: &serde_json::Value) -> SecurityConfig {
                 .map(|s| s.trim_start_matches("seccomp=").to_string())
         })
         .unwrap_or_default();
+    let seccomp_summary = crate::check::seccomp::summarize(&seccomp_profile);
+    let seccomp_profile = match &seccomp_summary {
+        Some(sum) if sum.source == "inline" => "inline".to_string(),
+        _ => seccomp_profile,
+    };
     
     let apparmor_profile = hc["SecurityOpt"].as_array()
         .and_then(|opts| {
@@ -602,72 +1191,136 @@ fn parse_security_config(c: &serde_json::Value) -> SecurityConfig {
     SecurityConfig {
         privileged: hc["Privileged"].as_bool().unwrap_or(false),
         capabilities,
+        cap_drop,
         seccomp_profile,
+        seccomp_summary,
         apparmor_profile,
+        applied_apparmor_profile: c["AppArmorProfile"].as_str().unwrap_or("").to_string(),
+        process_label: c["ProcessLabel"].as_str().unwrap_or("").to_string(),
+        mount_label: c["MountLabel"].as_str().unwrap_or("").to_string(),
         read_only_rootfs: hc["ReadonlyRootfs"].as_bool().unwrap_or(false),
         no_new_privileges: hc["NoNewPrivileges"].as_bool().unwrap_or(false),
+        pid_mode: hc["PidMode"].as_str().unwrap_or("").to_string(),
+        ipc_mode: hc["IpcMode"].as_str().unwrap_or("").to_string(),
+        group_add: hc["GroupAdd"].as_array()
+            .map(|arr| arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(String::from)
+                .collect())
+            .unwrap_or_default(),
+        effective_apparmor: None,
+        effective_selinux: None,
     }
 }
 
+/// 读取进程实际生效的 AppArmor profile 与 SELinux 上下文。
+/// attr/apparmor/current 为 LSM 栈化内核的专用接口；旧内核只有 attr/current，
+/// 其内容属于当前主 LSM，按宿主机启用的是哪一个归类
+fn read_mac_labels(pid: i32) -> (Option<String>, Option<String>) {
+    if pid <= 0 {
+        return (None, None);
+    }
+    let read = |name: &str| std::fs::read_to_string(format!("/proc/{}/attr/{}", pid, name))
+        .ok()
+        .map(|s| s.trim_end_matches(['\0', '\n']).to_string())
+        .filter(|s| !s.is_empty());
+    let selinux_host = std::path::Path::new("/sys/fs/selinux/enforce").exists();
+    let apparmor_host = std::path::Path::new("/sys/kernel/security/apparmor").exists();
+
+    let current = read("current");
+    let apparmor = read("apparmor/current")
+        .or_else(|| current.clone().filter(|_| apparmor_host && !selinux_host));
+    let selinux = current.filter(|_| selinux_host);
+    (apparmor, selinux)
+}
+
 // ── 用户和组收集 ─────────────────────────────────────────────────────────────
 
-fn collect_users_groups(container_id: &str) -> Result<Vec<UserGroupInfo>> {
-    use std::process::Command;
-    
-    // 获取容器内的所有用户
-    let users_output = Command::new("docker")
-        .args(&["exec", container_id, "getent", "passwd"])
-        .output()
-        .map_err(|e| SedockerError::Docker(format!("Failed to get users: {}", e)))?;
-    
-    if !users_output.status.success() {
-        return Ok(vec![]); // 容器可能没有 getent 或已停止
-    }
-    
-    let users_content = String::from_utf8_lossy(&users_output.stdout);
-    let mut users_groups = Vec::new();
-    
-    // 解析 /etc/passwd 格式: username:password:uid:gid:gecos:home:shell
-    for line in users_content.lines() {
-        let parts: Vec<&str> = line.split(':').collect();
-        if parts.len() >= 7 {
-            let username = parts[0].to_string();
-            let user_id = parts[2].parse().unwrap_or(0);
-            let group_id = parts[3].parse().unwrap_or(0);
-            let home_dir = if !parts[5].is_empty() { Some(parts[5].to_string()) } else { None };
-            let shell = if !parts[6].is_empty() { Some(parts[6].to_string()) } else { None };
-            
-            // 获取组名
-            let group_name = get_group_name(container_id, group_id).unwrap_or_else(|| group_id.to_string());
-            
-            users_groups.push(UserGroupInfo {
-                username,
-                user_id,
-                group_name,
-                group_id,
-                home_dir,
-                shell,
-            });
+/// 容器内 passwd/group 表，每个容器只获取一次
+///
+/// 优先 docker exec getent（走 NSS）；distroless 镜像没有 getent 时，
+/// 运行中的容器经 /proc/<pid>/root/etc 直接读取；scratch 镜像两者皆无，
+/// 之后所有 uid/gid 均按数字显示，不再反复 exec。
+#[derive(Default)]
+struct ContainerAccounts {
+    passwd: String,
+    groups: std::collections::HashMap<u32, String>,
+}
+
+impl ContainerAccounts {
+    fn fetch(container_id: &str, main_pid: i32) -> Self {
+        if let Some(passwd) = exec_getent(container_id, "passwd") {
+            let group = exec_getent(container_id, "group").unwrap_or_default();
+            return Self::from_tables(passwd, &group);
         }
+        if main_pid > 0 {
+            let etc = format!("/proc/{}/root/etc", main_pid);
+            if let Ok(passwd) = std::fs::read_to_string(format!("{}/passwd", etc)) {
+                let group = std::fs::read_to_string(format!("{}/group", etc)).unwrap_or_default();
+                return Self::from_tables(passwd, &group);
+            }
+        }
+        Self::default()
+    }
+
+    fn from_tables(passwd: String, group: &str) -> Self {
+        // /etc/group 格式: name:password:gid:members
+        let groups = group.lines()
+            .filter_map(|l| {
+                let parts: Vec<&str> = l.split(':').collect();
+                let gid = parts.get(2)?.parse().ok()?;
+                Some((gid, parts[0].to_string()))
+            })
+            .collect();
+        Self { passwd, groups }
+    }
+
+    /// 解析 /etc/passwd 格式: username:password:uid:gid:gecos:home:shell
+    fn passwd_entries(&self) -> impl Iterator<Item = Vec<&str>> {
+        self.passwd.lines()
+            .map(|l| l.split(':').collect::<Vec<&str>>())
+            .filter(|parts| parts.len() >= 7)
+    }
+
+    fn user_name(&self, uid: u32) -> String {
+        self.passwd_entries()
+            .find(|parts| parts[2].parse::<u32>().ok() == Some(uid))
+            .map(|parts| parts[0].to_string())
+            .unwrap_or_else(|| uid.to_string())
+    }
+
+    fn group_name(&self, gid: u32) -> String {
+        self.groups.get(&gid).cloned().unwrap_or_else(|| gid.to_string())
     }
-    
-    Ok(users_groups)
 }
 
-fn get_group_name(container_id: &str, gid: u32) -> Option<String> {
-    use std::process::Command;
-    
+fn exec_getent(container_id: &str, database: &str) -> Option<String> {
     let output = Command::new("docker")
-        .args(&["exec", container_id, "getent", "group", &gid.to_string()])
+        .args(["exec", container_id, "getent", database])
         .output()
         .ok()?;
     
     if !output.status.success() {
-        return None;
+        return None; // 容器可能没有 getent 或已停止
     }
-    
-    let content = String::from_utf8_lossy(&output.stdout);
-    content.split(':').next().map(|s| s.to_string())
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn collect_users_groups(accounts: &ContainerAccounts) -> Vec<UserGroupInfo> {
+    accounts.passwd_entries()
+        .map(|parts| {
+            let user_id: u32 = parts[2].parse().unwrap_or(0);
+            let group_id: u32 = parts[3].parse().unwrap_or(0);
+            UserGroupInfo {
+                username: parts[0].to_string(),
+                user_id,
+                group_name: accounts.group_name(group_id),
+                group_id,
+                home_dir: if !parts[5].is_empty() { Some(parts[5].to_string()) } else { None },
+                shell: if !parts[6].is_empty() { Some(parts[6].to_string()) } else { None },
+            }
+        })
+        .collect()
 }
 
 // ── 工具 ────────────────────────────────────────────────────────────────────
@@ -679,3 +1332,54 @@ fn str_val(c: &serde_json::Value, path: &[&str]) -> String {
     }
     cur.as_str().unwrap_or("").to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::os::unix::fs::{symlink, MetadataExt};
+
+    #[test]
+    fn net_dev_skips_loopback() {
+        let content = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 9999999      10    0    0    0     0          0         0  9999999      10    0    0    0     0       0          0
+  eth0: 1234567890123 5000    0    0    0     0          0         0 987654321098 4000    0    0    0     0       0          0
+  eth1:     100       1    0    0    0     0          0         0      200       2    0    0    0     0       0          0
+";
+        assert_eq!(parse_net_dev(content), Some((1234567890223, 987654321298)));
+        assert_eq!(parse_net_dev("Inter-|\n face |\n    lo: 1 0 0 0 0 0 0 0 1 0 0 0 0 0 0 0\n"), None);
+    }
+
+    #[test]
+    fn path_permissions_symlink_loops_and_aliases() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("real/sub")).unwrap();
+        std::fs::write(root.join("real/sub/data"), "x").unwrap();
+        // 自引用链接与指回上级的环
+        symlink("self", root.join("self")).unwrap();
+        symlink("..", root.join("real/sub/up")).unwrap();
+        // 同一目录经第二条路径可达；同一文件的硬链接
+        symlink("real", root.join("alias")).unwrap();
+        std::fs::hard_link(root.join("real/sub/data"), root.join("real/data-link")).unwrap();
+
+        let perms = collect_path_permissions(root.to_str().unwrap());
+
+        let inodes: Vec<(u64, u64)> = perms.iter()
+            .map(|p| {
+                let m = std::fs::symlink_metadata(&p.path).unwrap();
+                (m.dev(), m.ino())
+            })
+            .collect();
+        let unique: HashSet<_> = inodes.iter().collect();
+        assert_eq!(unique.len(), inodes.len(), "inode reported twice: {:?}",
+                   perms.iter().map(|p| &p.path).collect::<Vec<_>>());
+
+        // root、self、alias、real、sub、up、data（硬链接只计一次）
+        assert_eq!(perms.len(), 7);
+        // 链接本身记录但不跟随：不会出现 alias/ 或 up/ 下的路径
+        assert!(perms.iter().all(|p| !p.path.contains("alias/") && !p.path.contains("up/")));
+    }
+}
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
 
 // ── 顶层容器信息 ────────────────────────────────────────────────────────────
 
@@ -17,23 +19,39 @@ pub struct ContainerInfo {
     pub created: String,
     pub started_at: String,
     pub finished_at: String,
+    pub pid: i32,                 // 主进程宿主机 PID（State.Pid），未运行时为 0
+
+    // 由上面的时间戳计算（采集时刻），原始时间戳保留
+    pub age_seconds: Option<u64>,           // 距 created
+    pub image_age_seconds: Option<u64>,     // 距镜像 Created（docker image inspect）
+    pub uptime_seconds: Option<u64>,        // 距 started_at，仅 running
+    pub finished_ago_seconds: Option<u64>,  // 距 finished_at，仅非 running
+    pub status_age: String,                 // "up 3d 4h 5m" / "exited 2h 5m ago"
+    pub lifecycle: Lifecycle,               // 解析后的生命周期时间
+    pub timeline: Vec<TimelineEntry>,       // lifecycle + 健康检查 + docker events，按时间排序
+    pub last_death: Option<DeathInfo>,      // 最近一次退出：State 与 die / oom 事件合并
+    pub healthcheck_config: Option<HealthcheckConfig>,   // Config.Healthcheck；未配置或 NONE 为 None
 
     // 配置
     pub restart_policy: String,
     pub restart_count: i64,
+    pub auto_remove: bool,        // HostConfig.AutoRemove（--rm）：退出即删除，不留现场
     pub env: Vec<String>,         // verbose 下才填充
+    pub env_source: BTreeMap<String, EnvSource>,   // 变量名 → 来源（与镜像 Config.Env 对比）；镜像不可查时为空
     pub cmd: String,
     pub entrypoint: String,
     pub path: String,
     pub args: String,
     pub working_dir: String,
     pub user: String,
+    pub labels: BTreeMap<String, String>,
 
     // 安全配置
     pub security: SecurityConfig,
 
     // 网络
     pub ports: Vec<PortMapping>,
+    pub exposed_ports: Vec<String>,   // Config.ExposedPorts，如 "5432/tcp"；同网络容器可达，未必发布到宿主机
     pub networks: Vec<NetworkEntry>,
     pub network_mode: String,
 
@@ -46,14 +64,92 @@ pub struct ContainerInfo {
     // 资源使用（来自 docker stats，仅 running 容器）
     pub resource_usage: Option<ResourceUsage>,
 
+    // 主进程 OOM 评分（/proc/<pid>/oom_score{,_adj}，仅 running 容器）
+    pub oom_score: Option<i32>,
+    pub oom_score_adj: Option<i32>,
+
+    // 容器内 date 与宿主机的时钟差（仅 running 容器且 -v，docker exec）
+    pub clock: Option<ContainerClock>,
+
     // 日志 tail
     pub log_tail: Option<Vec<String>>,
+    pub log_truncated: bool,      // log_tail 只是尾部（-v 下超出 --log-max-bytes 的更早日志被丢弃）
+    pub log_findings: Vec<crate::check::logscan::LogFinding>,   // log_tail 中命中的故障特征
+
+    // 可写层变更（--diff，来自 docker diff）
+    pub fs_changes: Vec<FsChange>,
 
     // 进程信息（verbose，来自 docker top）
     pub processes: Vec<ProcessInfo>,
 
     // 用户和组信息
     pub users_groups: Vec<UserGroupInfo>,
+
+    // 原始 docker inspect（--raw-inspect），用于排查尚未建模的字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<serde_json::Value>,
+}
+
+/// 容器内时钟：`date +%s %z`，精度 1 秒
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerClock {
+    pub skew_seconds: i64,   // 容器时间 - 宿主机时间
+    pub utc_offset: String,  // 容器内时区偏移，如 +0800
+}
+
+/// 环境变量来源；inspect 不区分 --env 与 --env-file，两者都算运行时注入
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvSource {
+    Image,        // 镜像自带，值未改
+    Overridden,   // 镜像有同名变量，运行时改了值
+    Added,        // 运行时新增
+}
+
+// ── 生命周期 ────────────────────────────────────────────────────────────────
+
+/// State 中的时间戳（UTC），docker 零值为 None
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Lifecycle {
+    pub created: Option<DateTime<Utc>>,
+    pub started: Option<DateTime<Utc>>,
+    pub finished: Option<DateTime<Utc>>,
+    pub health_status: Option<String>,       // healthy / unhealthy / starting；无健康检查为 None
+    pub health_checks: Vec<HealthCheck>,     // State.Health.Log（docker 保留最近 5 次）
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheck {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub exit_code: i64,
+    pub output: String,
+}
+
+/// 配置的健康检查（Config.Healthcheck）；时长为 0 时 docker 使用默认值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthcheckConfig {
+    pub test: Vec<String>,          // ["CMD-SHELL", "curl -f localhost"] / ["CMD", ...]
+    pub interval_secs: u64,
+    pub timeout_secs: u64,
+    pub retries: u64,
+    pub start_period_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub time: DateTime<Utc>,
+    pub source: String,   // state / health / event
+    pub label: String,
+}
+
+/// 最近一次退出的原因
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeathInfo {
+    pub finished_at: DateTime<Utc>,
+    pub exit_code: i64,
+    pub oom_killed: bool,
+    pub signal: Option<String>,   // 退出码 > 128 时对应的信号名，如 SIGKILL
 }
 
 // ── 网络 ────────────────────────────────────────────────────────────────────
@@ -84,6 +180,16 @@ pub struct MountInfo {
     pub mode: String,
     pub rw: bool,
     pub permissions: Vec<PathPermission>,  // uid/gid for all files under mount
+    pub source_type: String,               // 宿主机侧 source：dir / file / other / missing（无 source 时为空）
+    pub tmpfs: Option<TmpfsOptions>,       // 仅 HostConfig.Tmpfs（--tmpfs）来源
+}
+
+/// --tmpfs 选项；Docker 默认 noexec,nosuid,nodev，显式 exec / suid 才放开
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TmpfsOptions {
+    pub size_bytes: Option<u64>,   // None：未设 size，内核默认上限为一半内存
+    pub exec: bool,
+    pub suid: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +200,21 @@ pub struct PathPermission {
     pub mode: u32,
 }
 
+/// docker diff 输出的一行：A 新增 / C 修改 / D 删除
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FsChangeKind {
+    Added,
+    Changed,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FsChange {
+    pub kind: FsChangeKind,
+    pub path: String,
+}
+
 // ── 资源 ────────────────────────────────────────────────────────────────────
 
 /// 来自 inspect HostConfig（静态配置）
@@ -105,6 +226,7 @@ pub struct ResourceConfig {
     pub memory_limit: u64, // 0 = unlimited
     pub memory_swap: i64,  // -1 = unlimited
     pub pids_limit: i64,   // 0 = unlimited
+    pub oom_kill_disable: bool,
 }
 
 /// 来自 docker stats（运行时实际用量）
@@ -119,6 +241,21 @@ pub struct ResourceUsage {
     pub net_rx: u64,
     pub net_tx: u64,
     pub pids: u64,
+    pub memory_breakdown: Option<MemoryBreakdown>,   // cgroup memory.stat；读不到为 None
+
+    // --sample 区间速率（bytes/s）；cpu_percent 此时为区间平均值
+    pub sample_secs: Option<u64>,
+    pub net_rx_rate: Option<f64>,
+    pub net_tx_rate: Option<f64>,
+    pub block_read_rate: Option<f64>,
+    pub block_write_rate: Option<f64>,
+}
+
+/// 内存用量拆分：页缓存可回收，rss 才代表真实压力
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryBreakdown {
+    pub rss: u64,     // v2 anon / v1 rss
+    pub cache: u64,   // v2 file / v1 cache
 }
 
 // ── 安全配置 ────────────────────────────────────────────────────────────────
@@ -126,11 +263,57 @@ pub struct ResourceUsage {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityConfig {
     pub privileged: bool,
-    pub capabilities: Vec<String>,
-    pub seccomp_profile: String,
-    pub apparmor_profile: String,
+    pub capabilities: Vec<String>,        // HostConfig.CapAdd
+    pub cap_drop: Vec<String>,            // HostConfig.CapDrop
+    pub seccomp_profile: String,          // 内联 JSON profile 记为 "inline"
+    pub seccomp_summary: Option<crate::check::seccomp::SeccompSummary>,
+    pub apparmor_profile: String,         // SecurityOpt 中请求的 profile
+    pub applied_apparmor_profile: String, // inspect 顶层 AppArmorProfile（实际生效）
+    pub process_label: String,            // SELinux 进程标签，如 system_u:system_r:container_t:s0:c123,c456
+    pub mount_label: String,
     pub read_only_rootfs: bool,
     pub no_new_privileges: bool,
+    pub group_add: Vec<String>,   // HostConfig.GroupAdd（--group-add），组名或数字 GID
+    pub pid_mode: String,         // HostConfig.PidMode：""（私有）/ host / container:<id>
+    pub ipc_mode: String,         // HostConfig.IpcMode：private / shareable / host / container:<id>
+    pub effective_apparmor: Option<String>, // /proc/<pid>/attr 实际生效的 profile，如 "docker-default (enforce)"
+    pub effective_selinux: Option<String>,  // /proc/<pid>/attr 实际生效的 SELinux 上下文
+}
+
+/// MAC 约束类别：未约束 / docker 默认策略 / 自定义策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacKind {
+    Unconfined,
+    Default,
+    Custom,
+}
+
+impl MacKind {
+    /// AppArmor："unconfined" 或 "<profile> (enforce|complain)"
+    pub fn of_apparmor(label: &str) -> Self {
+        match label.split_whitespace().next().unwrap_or("") {
+            "" | "unconfined" => MacKind::Unconfined,
+            "docker-default" => MacKind::Default,
+            _ => MacKind::Custom,
+        }
+    }
+
+    /// SELinux：按上下文 user:role:type:level 中的 type 判断
+    pub fn of_selinux(context: &str) -> Self {
+        match context.split(':').nth(2).unwrap_or("") {
+            "" | "unconfined_t" | "spc_t" => MacKind::Unconfined,
+            "container_t" | "svirt_lxc_net_t" => MacKind::Default,
+            _ => MacKind::Custom,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MacKind::Unconfined => "unconfined",
+            MacKind::Default => "default",
+            MacKind::Custom => "custom",
+        }
+    }
 }
 
 // ── 用户和组信息 ─────────────────────────────────────────────────────────────
@@ -149,7 +332,8 @@ pub struct UserGroupInfo {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
-    pub pid: i32,
+    pub pid: i32,                     // 宿主机 PID
+    pub container_pid: Option<i32>,   // 容器 PID 命名空间内的 PID（/proc/<pid>/status NSpid 末项）
     pub ppid: i32,
     pub uid: u32,
     pub gid: u32,
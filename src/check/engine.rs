@@ -3,6 +3,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::time::{Duration, Instant};
 use crate::utils::{Result, SedockerError};
 
 // ── 数据结构 ────────────────────────────────────────────────────────────────
@@ -13,6 +14,7 @@ pub struct EngineInfo {
     pub runtime: RuntimeInfo,
     pub daemon_config: DaemonConfig,
     pub daemon_logs: Vec<String>,     // 最近的 warning/error
+    pub storage: Option<DockerStorage>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,24 +53,59 @@ pub struct DaemonConfig {
     pub raw: Option<serde_json::Value>, // 原始内容（若存在）
 }
 
+/// DockerRootDir 所在文件系统及存储驱动目录占用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerStorage {
+    pub root_dir: String,
+    pub filesystem: String,          // df 第一列（设备）
+    pub mount: String,               // root_dir 所在挂载点
+    pub total_kb: u64,
+    pub used_kb: u64,
+    pub available_kb: u64,
+    pub used_percent: f64,
+    pub driver_dir: String,          // <root_dir>/<storage_driver>，如 overlay2
+    pub driver_used_kb: Option<u64>, // du 结果；目录不存在或无权限时为 None
+}
+
 // ── 收集入口 ────────────────────────────────────────────────────────────────
 
-pub fn collect(verbose: bool) -> Result<EngineInfo> {
+pub fn collect(verbose: u8) -> Result<EngineInfo> {
     let version = collect_version()?;
     let runtime = collect_runtime()?;
     let daemon_config = collect_daemon_config();
-    let daemon_logs = if verbose {
+    let daemon_logs = if verbose >= crate::check::VERBOSE_DETAIL {
         collect_daemon_logs(50)
     } else {
         collect_daemon_logs(20)
     };
 
-    Ok(EngineInfo { version, runtime, daemon_config, daemon_logs })
+    let storage = collect_storage(&runtime.root_dir, &runtime.storage_driver);
+
+    Ok(EngineInfo { version, runtime, daemon_config, daemon_logs, storage })
 }
 
 // ── docker version ──────────────────────────────────────────────────────────
 
-fn collect_version() -> Result<VersionInfo> {
+/// 开机时 dockerd 可能尚未就绪：每秒重试 docker version，超时返回最后一次的错误
+pub fn wait_for_daemon(timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+    let mut announced = false;
+    loop {
+        match collect_version() {
+            Ok(_) => return Ok(()),
+            Err(e) if Instant::now() >= deadline => return Err(e),
+            Err(_) => {
+                if !announced {
+                    eprintln!("Waiting up to {}s for Docker daemon...", timeout.as_secs());
+                    announced = true;
+                }
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+}
+
+pub fn collect_version() -> Result<VersionInfo> {
     // Try JSON format first
     let output = Command::new("docker")
         .args(&["version", "-f", "json"])
@@ -204,6 +241,49 @@ fn collect_runtime() -> Result<RuntimeInfo> {
     })
 }
 
+// ── Docker 存储 ─────────────────────────────────────────────────────────────
+
+/// host 磁盘列表会跳过 overlay，这里单独统计 DockerRootDir 所在分区和驱动目录
+fn collect_storage(root_dir: &str, driver: &str) -> Option<DockerStorage> {
+    if root_dir.is_empty() {
+        return None;
+    }
+
+    let out = Command::new("df").args(["-Pk", root_dir]).output().ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&out.stdout);
+    let parts: Vec<&str> = text.lines().nth(1)?.split_whitespace().collect();
+    if parts.len() < 6 {
+        return None;
+    }
+
+    let driver_dir = format!("{}/{}", root_dir.trim_end_matches('/'), driver);
+    // -x 不跨文件系统，避免把容器内的挂载算进去
+    let driver_used_kb = Command::new("du")
+        .args(["-sxk", &driver_dir])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8_lossy(&o.stdout)
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse().ok()));
+
+    Some(DockerStorage {
+        root_dir: root_dir.to_string(),
+        filesystem: parts[0].to_string(),
+        mount: parts[5].to_string(),
+        total_kb: parts[1].parse().unwrap_or(0),
+        used_kb: parts[2].parse().unwrap_or(0),
+        available_kb: parts[3].parse().unwrap_or(0),
+        used_percent: parts[4].trim_end_matches('%').parse().unwrap_or(0.0),
+        driver_dir,
+        driver_used_kb,
+    })
+}
+
 // ── daemon.json ─────────────────────────────────────────────────────────────
 
 fn collect_daemon_config() -> DaemonConfig {
@@ -227,6 +307,9 @@ fn collect_daemon_config() -> DaemonConfig {
 
 // ── daemon logs ─────────────────────────────────────────────────────────────
 
+/// journalctl 与 /var/log/docker.log 都读不到时的占位行
+pub const DAEMON_LOGS_UNAVAILABLE: &str = "daemon logs unavailable";
+
 fn collect_daemon_logs(lines: usize) -> Vec<String> {
     // 方法1: journalctl
     if let Ok(o) = Command::new("journalctl")
@@ -250,19 +333,69 @@ fn collect_daemon_logs(lines: usize) -> Vec<String> {
         }
     }
 
-    // 方法2: /var/log/docker.log
-    if let Ok(content) = std::fs::read_to_string("/var/log/docker.log") {
-        return content.lines()
-            .rev()
-            .take(lines)
-            .map(|l| l.to_string())
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
+    // 方法2: /var/log/docker.log 及轮转文件（docker.log.1、docker.log.2.gz ...）
+    if let Some(all) = read_rotated_log(DOCKER_LOG_DIR, DOCKER_LOG_NAME) {
+        let matched: Vec<String> = all.into_iter()
+            .filter(|l| is_warning_line(l))
             .collect();
+        let start = matched.len().saturating_sub(lines);
+        return matched[start..].to_vec();
     }
 
-    vec!["daemon logs unavailable".to_string()]
+    vec![DAEMON_LOGS_UNAVAILABLE.to_string()]
+}
+
+const DOCKER_LOG_DIR: &str = "/var/log";
+const DOCKER_LOG_NAME: &str = "docker.log";
+
+/// 除当前文件外最多读取的轮转文件数
+const MAX_ROTATED_LOGS: usize = 3;
+
+/// 当前日志与最近的轮转文件按时间顺序拼接（.gz 解压）；当前文件不存在时为 None
+fn read_rotated_log(dir: &str, name: &str) -> Option<Vec<String>> {
+    let current = std::fs::read_to_string(format!("{}/{}", dir, name)).ok()?;
+
+    // name.N / name.N.gz，N 越大越旧
+    let prefix = format!("{}.", name);
+    let mut rotated: Vec<(u32, std::path::PathBuf)> = std::fs::read_dir(dir).ok()?
+        .flatten()
+        .filter_map(|e| {
+            let file = e.file_name().to_str()?.to_string();
+            let rest = file.strip_prefix(&prefix)?;
+            let n = rest.strip_suffix(".gz").unwrap_or(rest).parse().ok()?;
+            Some((n, e.path()))
+        })
+        .collect();
+    rotated.sort_by_key(|(n, _)| *n);
+    rotated.truncate(MAX_ROTATED_LOGS);
+
+    let mut out = Vec::new();
+    for (_, path) in rotated.iter().rev() {
+        if let Some(text) = read_maybe_gz(path) {
+            out.extend(text.lines().map(String::from));
+        }
+    }
+    out.extend(current.lines().map(String::from));
+    Some(out)
+}
+
+fn read_maybe_gz(path: &std::path::Path) -> Option<String> {
+    use std::io::Read;
+    let file = std::fs::File::open(path).ok()?;
+    let mut bytes = Vec::new();
+    if path.extension().is_some_and(|e| e == "gz") {
+        flate2::read::GzDecoder::new(file).read_to_end(&mut bytes).ok()?;
+    } else {
+        std::io::BufReader::new(file).read_to_end(&mut bytes).ok()?;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// 与 journalctl -p warning 对应：logrus 的 level=warning / error / fatal / panic
+fn is_warning_line(line: &str) -> bool {
+    ["level=warn", "level=error", "level=fatal", "level=panic"]
+        .iter()
+        .any(|l| line.contains(l))
 }
 
 // ── 工具 ────────────────────────────────────────────────────────────────────
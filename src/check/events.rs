@@ -1,14 +1,18 @@
 //! Docker 事件收集
-//! 来源：docker events --since <duration>
+//! 来源：docker events --since <duration>（历史）/ docker events（实时流）
 
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use crate::utils::{Result, SedockerError};
+use crate::utils::id::short_id;
 
 const DEFAULT_SINCE: &str = "24h";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerEvent {
     pub timestamp: String,
+    pub time: Option<chrono::DateTime<chrono::Utc>>,   // 由 timeNano 解析，用于时间线合并
     pub event_type: String,   // container / network / volume / image
     pub action: String,       // start / stop / die / kill / oom / ...
     pub actor_id: String,     // short container id or name
@@ -17,58 +21,76 @@ pub struct DockerEvent {
 }
 
 pub fn collect(since: &str) -> Vec<DockerEvent> {
-    let out = match Command::new("docker")
-        .args(&[
+    fetch(since).unwrap_or_else(|e| {
+        eprintln!("warn: {}", e);
+        vec![]
+    })
+}
+
+pub fn collect_with_limit(since: &str, limit: usize) -> Vec<DockerEvent> {
+    let mut events = collect(since);
+    events.truncate(limit);
+    events
+}
+
+/// 历史事件（--since 至今）；docker 调用失败时返回错误
+pub fn fetch(since: &str) -> Result<Vec<DockerEvent>> {
+    let out = Command::new("docker")
+        .args([
             "events",
             "--since", since,
             "--until", "0s",
             "--format", "{{json .}}",
         ])
         .output()
-    {
-        Ok(o) if o.status.success() => o,
-        Ok(o) => {
-            eprintln!("warn: docker events: {}", String::from_utf8_lossy(&o.stderr));
-            return vec![];
-        }
-        Err(e) => {
-            eprintln!("warn: docker events failed: {}", e);
-            return vec![];
-        }
-    };
+        .map_err(|e| SedockerError::Docker(format!("docker events failed: {}", e)))?;
+    if !out.status.success() {
+        return Err(SedockerError::Docker(format!("docker events: {}",
+            String::from_utf8_lossy(&out.stderr).trim())));
+    }
 
-    String::from_utf8_lossy(&out.stdout)
+    Ok(String::from_utf8_lossy(&out.stdout)
         .lines()
-        .filter_map(|line| parse_event_line(line))
-        .collect()
+        .filter_map(parse_event_line)
+        .collect())
 }
 
-pub fn collect_with_limit(since: &str, limit: usize) -> Vec<DockerEvent> {
-    let out = match Command::new("docker")
-        .args(&[
-            "events",
-            "--since", since,
-            "--until", "0s",
-            "--format", "{{json .}}",
-        ])
-        .output()
-    {
-        Ok(o) if o.status.success() => o,
-        Ok(o) => {
-            eprintln!("warn: docker events: {}", String::from_utf8_lossy(&o.stderr));
-            return vec![];
-        }
-        Err(e) => {
-            eprintln!("warn: docker events failed: {}", e);
-            return vec![];
+/// action 匹配：精确匹配，或匹配 "exec_start: sh" / "health_status: healthy" 中冒号前的部分
+pub fn action_matches(action: &str, wanted: &str) -> bool {
+    action == wanted
+        || action.strip_prefix(wanted).is_some_and(|rest| rest.starts_with(':'))
+}
+
+/// 实时跟随 docker events，每收到一条事件调用一次 `on_event`
+/// Ctrl+C 时终止 docker 子进程，读取循环随 stdout 关闭而结束
+pub fn follow<F: FnMut(&DockerEvent)>(mut on_event: F) -> Result<()> {
+    let mut child = Command::new("docker")
+        .args(["events", "--format", "{{json .}}"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| SedockerError::Docker(format!("docker events failed: {}", e)))?;
+
+    let child_pid = child.id() as libc::pid_t;
+    ctrlc::set_handler(move || {
+        eprintln!("\nCtrl+C received, stopping event stream...");
+        unsafe { libc::kill(child_pid, libc::SIGTERM); }
+    }).map_err(|e| SedockerError::System(format!("Error setting Ctrl-C handler: {}", e)))?;
+
+    let stdout = child.stdout.take()
+        .ok_or_else(|| SedockerError::Docker("docker events: no stdout".to_string()))?;
+
+    for line in BufReader::new(stdout).lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(_) => break,
+        };
+        if let Some(ev) = parse_event_line(&line) {
+            on_event(&ev);
         }
-    };
+    }
 
-    String::from_utf8_lossy(&out.stdout)
-        .lines()
-        .filter_map(|line| parse_event_line(line))
-        .take(limit)
-        .collect()
+    let _ = child.wait();
+    Ok(())
 }
 
 fn parse_event_line(line: &str) -> Option<DockerEvent> {
@@ -85,11 +107,13 @@ fn parse_event_line(line: &str) -> Option<DockerEvent> {
         })
         .unwrap_or_else(|| j["timeNano"].as_str().unwrap_or("").to_string());
 
+    let time = j["timeNano"].as_i64()
+        .map(chrono::DateTime::from_timestamp_nanos)
+        .or_else(|| j["time"].as_i64().and_then(|t| chrono::DateTime::from_timestamp(t, 0)));
+
     let event_type = j["Type"].as_str().unwrap_or("").to_string();
     let action     = j["Action"].as_str().unwrap_or("").to_string();
-    let actor_id   = j["Actor"]["ID"].as_str()
-        .unwrap_or("")
-        .chars().take(12).collect::<String>();
+    let actor_id   = short_id(j["Actor"]["ID"].as_str().unwrap_or(""));
 
     let attributes: std::collections::HashMap<String, String> = j["Actor"]["Attributes"]
         .as_object()
@@ -104,6 +128,7 @@ fn parse_event_line(line: &str) -> Option<DockerEvent> {
 
     Some(DockerEvent {
         timestamp: ts,
+        time,
         event_type,
         action,
         actor_id,
@@ -0,0 +1,49 @@
+//! 容器筛选：在 docker inspect 之后、昂贵的 stats/logs/exec 采集之前生效
+
+use crate::cli::CheckArgs;
+
+/// compose 为容器打上的项目名标签
+pub const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+
+pub struct ContainerFilter {
+    pub image: Option<String>,
+    pub project: Option<String>,
+    pub exclude: Vec<String>,
+}
+
+impl ContainerFilter {
+    pub fn from_args(args: &CheckArgs) -> Self {
+        Self {
+            image: args.image.clone(),
+            project: args.project.clone(),
+            exclude: args.exclude_containers.clone(),
+        }
+    }
+
+    /// 基于原始 inspect JSON 判断容器是否保留
+    pub fn matches(&self, inspect: &serde_json::Value) -> bool {
+        if let Some(pattern) = &self.image {
+            // Config.Image 为创建容器时指定的镜像名，子串匹配（含完全匹配）
+            let image = inspect["Config"]["Image"].as_str().unwrap_or("");
+            if !image.contains(pattern.as_str()) {
+                return false;
+            }
+        }
+        if let Some(project) = &self.project {
+            // compose 项目名精确匹配
+            let label = inspect["Config"]["Labels"][COMPOSE_PROJECT_LABEL].as_str();
+            if label != Some(project.as_str()) {
+                return false;
+            }
+        }
+        // 排除放在最后：ID 前缀或去掉 '/' 的容器名精确匹配
+        if !self.exclude.is_empty() {
+            let id = inspect["Id"].as_str().unwrap_or("");
+            let name = inspect["Name"].as_str().unwrap_or("").trim_start_matches('/');
+            if self.exclude.iter().any(|e| id.starts_with(e.as_str()) || name == e) {
+                return false;
+            }
+        }
+        true
+    }
+}
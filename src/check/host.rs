@@ -2,6 +2,7 @@
 //! 来源：/proc/*, /etc/os-release, /sys/fs/cgroup, 系统命令
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use crate::utils::{Result, SedockerError};
 
@@ -12,22 +13,28 @@ pub struct HostInfo {
     pub os: OsInfo,
     pub cpu: CpuInfo,
     pub memory: MemoryInfo,
+    pub pressure: Option<PressureInfo>,   // None = 内核不支持 PSI
     pub disk: Vec<DiskInfo>,
     pub cgroup_version: String,   // "v1" / "v2"
+    pub sysctls: BTreeMap<String, String>,   // 与容器相关的内核参数，读取失败的项省略
+    pub inotify: InotifyUsage,
     pub security: SecurityInfo,
     pub time: TimeInfo,
+    pub docker_socket: Option<DockerSocket>,   // None：DOCKER_HOST 非 unix 或 socket 不存在
+    pub docker_group: Vec<DockerGroupMember>,  // docker 组成员（不含 root），等同 root 权限
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct OsInfo {
     pub hostname: String,
     pub os_release: String,       // PRETTY_NAME
     pub kernel: String,           // uname -r
     pub arch: String,
     pub uptime_seconds: u64,
+    pub kernel_cmdline: String,   // /proc/cmdline
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CpuInfo {
     pub model: String,
     pub logical_cores: u32,
@@ -36,7 +43,7 @@ pub struct CpuInfo {
     pub load_avg_15: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MemoryInfo {
     pub total_kb: u64,
     pub available_kb: u64,
@@ -46,6 +53,17 @@ pub struct MemoryInfo {
     pub swap_used_kb: u64,
 }
 
+/// 内存压力（PSI，/proc/pressure/memory），单位：% 时间
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PressureInfo {
+    pub some_avg10: f64,
+    pub some_avg60: f64,
+    pub some_avg300: f64,
+    pub full_avg10: f64,
+    pub full_avg60: f64,
+    pub full_avg300: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiskInfo {
     pub mount: String,
@@ -57,29 +75,74 @@ pub struct DiskInfo {
     pub inode_used_percent: f64,
 }
 
+/// inotify/fanotify 使用量估算（遍历 /proc/*/fd），限制按用户计算，
+/// 因此同时给出占用最多的 uid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InotifyUsage {
+    pub max_user_watches: u64,
+    pub max_user_instances: u64,
+    pub total_instances: u64,
+    pub total_watches: u64,
+    pub top_uid: Option<u32>,
+    pub top_uid_instances: u64,
+    pub top_uid_watches: u64,
+    pub fanotify_groups: u64,
+    pub fanotify_max_user_groups: Option<u64>,   // 5.13 之前的内核没有该 sysctl
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityInfo {
     pub selinux: String,     // "enforcing" / "permissive" / "disabled" / "unavailable"
     pub apparmor: String,    // "enabled" / "disabled" / "unavailable"
 }
 
+/// docker.sock 的属主与权限；能访问它即等同宿主机 root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerSocket {
+    pub path: String,
+    pub owner: String,
+    pub group: String,
+    pub mode: u32,                          // 权限位（如 0o660）
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerGroupMember {
+    pub name: String,
+    pub uid: Option<u32>,      // /etc/passwd 中找不到时为 None
+    pub interactive: bool,     // 普通登录账户（uid >= UID_MIN 且 shell 可登录），非服务账户
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeInfo {
     pub system_time: String,
     pub ntp_synced: bool,
+    pub ntp_offset_ms: Option<f64>,   // 本机相对 NTP 的偏差，正值为快；chronyc / timedatectl 都不可用时为 None
 }
 
 // ── 收集入口 ────────────────────────────────────────────────────────────────
 
-pub fn collect() -> Result<HostInfo> {
-    Ok(HostInfo {
-        os:             collect_os()?,
-        cpu:            collect_cpu()?,
-        memory:         collect_memory()?,
-        disk:           collect_disk()?,
+/// 各子项独立采集：某项失败时用默认值代替并记入 warnings，不中断整个报告
+pub fn collect(warnings: &mut Vec<String>) -> HostInfo {
+    HostInfo {
+        os:             or_warn("host os", collect_os(), warnings),
+        cpu:            or_warn("host cpu", collect_cpu(), warnings),
+        memory:         or_warn("host memory", collect_memory(), warnings),
+        pressure:       collect_memory_pressure(),
+        disk:           or_warn("host disk", collect_disk(), warnings),
         cgroup_version: detect_cgroup_version(),
+        sysctls:        collect_sysctls(),
+        inotify:        collect_inotify_usage(),
         security:       collect_security(),
         time:           collect_time(),
+        docker_socket:  collect_docker_socket(),
+        docker_group:   collect_docker_group(),
+    }
+}
+
+fn or_warn<T: Default>(section: &str, result: Result<T>, warnings: &mut Vec<String>) -> T {
+    result.unwrap_or_else(|e| {
+        warnings.push(format!("{}: {}", section, e));
+        T::default()
     })
 }
 
@@ -111,7 +174,12 @@ fn collect_os() -> Result<OsInfo> {
         .map(|v| v as u64)
         .unwrap_or(0);
 
-    Ok(OsInfo { hostname, os_release, kernel, arch, uptime_seconds })
+    let kernel_cmdline = fs::read_to_string("/proc/cmdline")
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    Ok(OsInfo { hostname, os_release, kernel, arch, uptime_seconds, kernel_cmdline })
 }
 
 fn parse_os_release() -> String {
@@ -158,7 +226,7 @@ fn parse_loadavg() -> (f64, f64, f64) {
 
 fn collect_memory() -> Result<MemoryInfo> {
     let meminfo = fs::read_to_string("/proc/meminfo")
-        .map_err(|e| SedockerError::Io(e))?;
+        .map_err(|e| SedockerError::System(format!("/proc/meminfo: {}", e)))?;
 
     let get = |key: &str| -> u64 {
         meminfo.lines()
@@ -189,6 +257,34 @@ fn collect_memory() -> Result<MemoryInfo> {
     })
 }
 
+// ── Memory pressure (PSI) ───────────────────────────────────────────────────
+
+fn collect_memory_pressure() -> Option<PressureInfo> {
+    // 格式：
+    // some avg10=0.00 avg60=0.00 avg300=0.00 total=0
+    // full avg10=0.00 avg60=0.00 avg300=0.00 total=0
+    let content = fs::read_to_string("/proc/pressure/memory").ok()?;
+
+    let parse_line = |prefix: &str| -> (f64, f64, f64) {
+        let line = content.lines().find(|l| l.starts_with(prefix)).unwrap_or("");
+        let get = |key: &str| -> f64 {
+            line.split_whitespace()
+                .find_map(|kv| kv.strip_prefix(key))
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.0)
+        };
+        (get("avg10="), get("avg60="), get("avg300="))
+    };
+
+    let (some_avg10, some_avg60, some_avg300) = parse_line("some");
+    let (full_avg10, full_avg60, full_avg300) = parse_line("full");
+
+    Some(PressureInfo {
+        some_avg10, some_avg60, some_avg300,
+        full_avg10, full_avg60, full_avg300,
+    })
+}
+
 // ── Disk ────────────────────────────────────────────────────────────────────
 
 fn collect_disk() -> Result<Vec<DiskInfo>> {
@@ -259,13 +355,105 @@ fn parse_inode_percents(output: &Option<std::process::Output>) -> std::collectio
     map
 }
 
+// ── sysctl ──────────────────────────────────────────────────────────────────
+
+const SYSCTL_KEYS: &[&str] = &[
+    "net.ipv4.ip_forward",
+    "net.bridge.bridge-nf-call-iptables",
+    "vm.swappiness",
+    "kernel.pid_max",
+    "fs.inotify.max_user_watches",
+];
+
+fn collect_sysctls() -> BTreeMap<String, String> {
+    SYSCTL_KEYS.iter()
+        .filter_map(|key| {
+            let path = format!("/proc/sys/{}", key.replace('.', "/"));
+            fs::read_to_string(path).ok().map(|v| (key.to_string(), v.trim().to_string()))
+        })
+        .collect()
+}
+
+// ── inotify / fanotify ──────────────────────────────────────────────────────
+
+fn read_sysctl_u64(path: &str) -> Option<u64> {
+    fs::read_to_string(path).ok().and_then(|v| v.trim().parse().ok())
+}
+
+fn collect_inotify_usage() -> InotifyUsage {
+    use std::collections::HashMap;
+    use std::os::unix::fs::MetadataExt;
+
+    // uid -> (instances, watches)
+    let mut per_uid: HashMap<u32, (u64, u64)> = HashMap::new();
+    let mut fanotify_groups = 0u64;
+
+    if let Ok(procs) = fs::read_dir("/proc") {
+        for p in procs.flatten() {
+            let name = p.file_name();
+            let pid = match name.to_str() {
+                Some(n) if n.bytes().all(|b| b.is_ascii_digit()) => n.to_string(),
+                _ => continue,
+            };
+            let uid = match p.metadata() {
+                Ok(m) => m.uid(),
+                Err(_) => continue,
+            };
+            let fds = match fs::read_dir(format!("/proc/{}/fd", pid)) {
+                Ok(f) => f,
+                Err(_) => continue,
+            };
+            for fd in fds.flatten() {
+                let target = match fs::read_link(fd.path()) {
+                    Ok(t) => t,
+                    Err(_) => continue,
+                };
+                let target = target.to_string_lossy();
+                if target == "anon_inode:inotify" {
+                    // fdinfo 中每个 "inotify wd:" 行对应一个 watch
+                    let watches = fs::read_to_string(
+                        format!("/proc/{}/fdinfo/{}", pid, fd.file_name().to_string_lossy()))
+                        .map(|c| c.lines().filter(|l| l.starts_with("inotify wd:")).count() as u64)
+                        .unwrap_or(0);
+                    let entry = per_uid.entry(uid).or_insert((0, 0));
+                    entry.0 += 1;
+                    entry.1 += watches;
+                } else if target == "anon_inode:[fanotify]" {
+                    fanotify_groups += 1;
+                }
+            }
+        }
+    }
+
+    let total_instances = per_uid.values().map(|v| v.0).sum();
+    let total_watches   = per_uid.values().map(|v| v.1).sum();
+    let top = per_uid.iter().max_by_key(|(_, v)| v.1);
+
+    InotifyUsage {
+        max_user_watches:   read_sysctl_u64("/proc/sys/fs/inotify/max_user_watches").unwrap_or(0),
+        max_user_instances: read_sysctl_u64("/proc/sys/fs/inotify/max_user_instances").unwrap_or(0),
+        total_instances,
+        total_watches,
+        top_uid:            top.map(|(uid, _)| *uid),
+        top_uid_instances:  top.map(|(_, v)| v.0).unwrap_or(0),
+        top_uid_watches:    top.map(|(_, v)| v.1).unwrap_or(0),
+        fanotify_groups,
+        fanotify_max_user_groups: read_sysctl_u64("/proc/sys/fs/fanotify/max_user_groups"),
+    }
+}
+
 // ── cgroup ──────────────────────────────────────────────────────────────────
 
-fn detect_cgroup_version() -> String {
-    // cgroup v2: /sys/fs/cgroup/cgroup.controllers 存在
-    if std::path::Path::new("/sys/fs/cgroup/cgroup.controllers").exists() {
+pub fn detect_cgroup_version() -> String {
+    cgroup_version_at(std::path::Path::new("/sys/fs/cgroup"))
+}
+
+/// 按 cgroup 挂载根判断版本；混合布局（v1 控制器 + unified 子目录）记为 v1
+pub fn cgroup_version_at(root: &std::path::Path) -> String {
+    // cgroup v2: <root>/cgroup.controllers 存在
+    if root.join("cgroup.controllers").exists() {
         "v2".to_string()
-    } else if std::path::Path::new("/sys/fs/cgroup/memory/memory.limit_in_bytes").exists() {
+    } else if root.join("memory/memory.limit_in_bytes").exists() {
         "v1".to_string()
     } else {
         "unknown".to_string()
@@ -307,6 +495,95 @@ fn read_apparmor_status() -> String {
     }
 }
 
+// ── docker.sock ─────────────────────────────────────────────────────────────
+
+const DEFAULT_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+fn collect_docker_socket() -> Option<DockerSocket> {
+    use std::os::unix::fs::MetadataExt;
+
+    // DOCKER_HOST=unix:///path 时以其为准；tcp:// 等非本地 socket 无可检查的文件
+    let path = match std::env::var("DOCKER_HOST") {
+        Ok(h) if !h.is_empty() => h.strip_prefix("unix://")?.to_string(),
+        _ => DEFAULT_DOCKER_SOCKET.to_string(),
+    };
+    let meta = fs::metadata(&path).ok()?;
+    let groups = fs::read_to_string("/etc/group").unwrap_or_default();
+    let passwd = fs::read_to_string("/etc/passwd").unwrap_or_default();
+
+    Some(DockerSocket {
+        path,
+        owner: id_name(&passwd, meta.uid()),
+        group: id_name(&groups, meta.gid()),
+        mode: meta.mode() & 0o7777,
+    })
+}
+
+/// 普通用户 UID 起点（/etc/login.defs 未设置时的通用默认值）
+const DEFAULT_UID_MIN: u32 = 1000;
+
+/// docker 组的附加成员 + 以 docker 为主组的用户，排除 root
+fn collect_docker_group() -> Vec<DockerGroupMember> {
+    let groups = fs::read_to_string("/etc/group").unwrap_or_default();
+    let passwd = fs::read_to_string("/etc/passwd").unwrap_or_default();
+    let docker_gid = groups.lines()
+        .map(|l| l.split(':').collect::<Vec<_>>())
+        .find(|p| p[0] == "docker")
+        .and_then(|p| p.get(2)?.parse::<u32>().ok());
+    let docker_gid = match docker_gid {
+        Some(g) => g,
+        None => return vec![],
+    };
+    let uid_min = fs::read_to_string("/etc/login.defs").ok()
+        .and_then(|c| c.lines()
+            .find_map(|l| l.trim().strip_prefix("UID_MIN")?.trim().parse().ok()))
+        .unwrap_or(DEFAULT_UID_MIN);
+
+    // name → (uid, gid, shell)
+    let accounts: Vec<(&str, u32, u32, &str)> = passwd.lines()
+        .filter_map(|l| {
+            let p: Vec<&str> = l.split(':').collect();
+            Some((p[0], p.get(2)?.parse().ok()?, p.get(3)?.parse().ok()?, *p.get(6)?))
+        })
+        .collect();
+
+    let mut names = group_members(&groups, "docker");
+    for (name, _, gid, _) in &accounts {
+        if *gid == docker_gid && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+
+    names.into_iter()
+        .filter(|n| n != "root")
+        .map(|name| {
+            let acct = accounts.iter().find(|a| a.0 == name);
+            let interactive = acct.is_some_and(|(_, uid, _, shell)| {
+                *uid >= uid_min && !shell.ends_with("nologin") && !shell.ends_with("false")
+            });
+            DockerGroupMember { uid: acct.map(|a| a.1), name, interactive }
+        })
+        .collect()
+}
+
+/// /etc/passwd 或 /etc/group 中第三列为 id 的名称，找不到时返回数字
+fn id_name(table: &str, id: u32) -> String {
+    table.lines()
+        .find_map(|l| {
+            let parts: Vec<&str> = l.split(':').collect();
+            (parts.get(2)?.parse::<u32>().ok()? == id).then(|| parts[0].to_string())
+        })
+        .unwrap_or_else(|| id.to_string())
+}
+
+fn group_members(groups: &str, name: &str) -> Vec<String> {
+    groups.lines()
+        .find(|l| l.split(':').next() == Some(name))
+        .and_then(|l| l.split(':').nth(3))
+        .map(|m| m.split(',').filter(|u| !u.is_empty()).map(String::from).collect())
+        .unwrap_or_default()
+}
+
 // ── Time ────────────────────────────────────────────────────────────────────
 
 fn collect_time() -> TimeInfo {
@@ -314,8 +591,39 @@ fn collect_time() -> TimeInfo {
 
     // timedatectl 检查 NTP，失败时回退到 /run/systemd/timesync/synchronized
     let ntp_synced = check_ntp_sync();
+    let ntp_offset_ms = chrony_offset_ms().or_else(timesyncd_offset_ms);
+
+    TimeInfo { system_time, ntp_synced, ntp_offset_ms }
+}
 
-    TimeInfo { system_time, ntp_synced }
+/// chronyc tracking："System time     : 0.000012345 seconds fast of NTP time"
+fn chrony_offset_ms() -> Option<f64> {
+    let o = std::process::Command::new("chronyc").arg("tracking").output().ok()?;
+    let out = String::from_utf8_lossy(&o.stdout);
+    let line = out.lines().find(|l| l.trim_start().starts_with("System time"))?;
+    let rest = line.split_once(':')?.1;
+    let mut parts = rest.split_whitespace();
+    let secs: f64 = parts.next()?.parse().ok()?;
+    let sign = if rest.contains("slow") { -1.0 } else { 1.0 };
+    Some(sign * secs * 1000.0)
+}
+
+/// timedatectl timesync-status："Offset: -1.234ms"（单位 us / ms / s）
+/// 该值为需要施加的校正量，取反即为本机偏差
+fn timesyncd_offset_ms() -> Option<f64> {
+    let o = std::process::Command::new("timedatectl").arg("timesync-status").output().ok()?;
+    let out = String::from_utf8_lossy(&o.stdout);
+    let value = out.lines().find_map(|l| l.trim_start().strip_prefix("Offset:"))?.trim();
+    let (num, scale) = if let Some(v) = value.strip_suffix("us") {
+        (v, 0.001)
+    } else if let Some(v) = value.strip_suffix("ms") {
+        (v, 1.0)
+    } else if let Some(v) = value.strip_suffix('s') {
+        (v, 1000.0)
+    } else {
+        return None;
+    };
+    num.trim().parse::<f64>().ok().map(|v| -v * scale)
 }
 
 fn check_ntp_sync() -> bool {
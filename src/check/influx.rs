@@ -0,0 +1,59 @@
+//! InfluxDB line protocol 输出（-o influx）
+//! 每个容器一个 sedock_container 点，宿主机一个 sedock_host 点，时间戳为纳秒
+
+use crate::check::report::CheckReport;
+use std::fmt::Write;
+
+pub fn render(report: &CheckReport) -> String {
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+    let mut out = String::new();
+
+    // ── 宿主机 ────────────────────────────────────────────────────────────
+    let h = &report.host;
+    let _ = writeln!(out,
+        "sedock_host,host={} load1={},load5={},load15={},mem_total_kb={}i,mem_used_kb={}i,mem_used_percent={},swap_used_kb={}i,uptime_seconds={}i {}",
+        escape_tag(&h.os.hostname),
+        h.cpu.load_avg_1, h.cpu.load_avg_5, h.cpu.load_avg_15,
+        h.memory.total_kb, h.memory.used_kb, h.memory.used_percent,
+        h.memory.swap_used_kb, h.os.uptime_seconds, ts);
+
+    for d in &h.disk {
+        let _ = writeln!(out,
+            "sedock_disk,host={},mount={},filesystem={} used_kb={}i,total_kb={}i,used_percent={},inode_used_percent={} {}",
+            escape_tag(&h.os.hostname), escape_tag(&d.mount), escape_tag(&d.filesystem),
+            d.used_kb, d.total_kb, d.used_percent, d.inode_used_percent, ts);
+    }
+
+    // ── 容器 ──────────────────────────────────────────────────────────────
+    for c in &report.containers {
+        let mut fields = format!(
+            "running={},restart_count={}i,exit_code={}i,oom_killed={},privileged={}",
+            c.status == "running", c.restart_count, c.exit_code, c.oom_killed, c.security.privileged);
+        if let Some(u) = &c.resource_usage {
+            let _ = write!(fields,
+                ",cpu={},mem_bytes={}i,mem_limit_bytes={}i,mem_percent={},net_rx_bytes={}i,net_tx_bytes={}i,blk_read_bytes={}i,blk_write_bytes={}i,pids={}i",
+                u.cpu_percent, u.memory_usage, u.memory_limit, u.memory_percent,
+                u.net_rx, u.net_tx, u.block_read, u.block_write, u.pids);
+        }
+        let _ = writeln!(out, "sedock_container,host={},name={},image={},status={} {} {}",
+            escape_tag(&h.os.hostname), escape_tag(&c.name), escape_tag(&c.image),
+            escape_tag(&c.status), fields, ts);
+    }
+
+    out
+}
+
+/// tag key/value 中的逗号、空格、等号需反斜杠转义；空值的 tag 不合法，用 "none" 代替
+fn escape_tag(s: &str) -> String {
+    if s.is_empty() {
+        return "none".to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        if matches!(ch, ',' | ' ' | '=' | '\\') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
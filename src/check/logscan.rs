@@ -0,0 +1,64 @@
+//! 日志扫描：在已获取的 log_tail 中匹配常见故障特征，统计每个模式的命中次数
+
+use crate::check::container::ContainerInfo;
+use crate::utils::{Result, SedockerError};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// 默认特征（名称, 正则）；--log-pattern 指定时整体替换
+const DEFAULT_PATTERNS: &[(&str, &str)] = &[
+    ("panic",              r"(?i)\bpanic(ked)?\b|\bfatal error\b"),
+    ("out of memory",      r"(?i)out of memory|\boom\b|cannot allocate memory"),
+    ("permission denied",  r"(?i)permission denied|operation not permitted"),
+    ("stack trace",        r"Traceback \(most recent call last\)|^\s+at [\w.$]+\(|^goroutine \d+ \["),
+    ("connection refused", r"(?i)connection refused|connection reset"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogFinding {
+    pub pattern: String,
+    pub count: usize,
+    pub example: String,   // 最近一条命中的行
+}
+
+pub struct LogScanner {
+    patterns: Vec<(String, Regex)>,
+}
+
+impl LogScanner {
+    /// custom 为空时使用默认特征；正则非法时立即报错
+    pub fn new(custom: &[String]) -> Result<Self> {
+        let specs: Vec<(String, String)> = if custom.is_empty() {
+            DEFAULT_PATTERNS.iter().map(|(n, r)| (n.to_string(), r.to_string())).collect()
+        } else {
+            custom.iter().map(|r| (r.clone(), r.clone())).collect()
+        };
+
+        let patterns = specs.into_iter()
+            .map(|(name, re)| Regex::new(&re)
+                .map(|r| (name, r))
+                .map_err(|e| SedockerError::Parse(format!("--log-pattern '{}': {}", re, e))))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { patterns })
+    }
+
+    pub fn scan(&self, lines: &[String]) -> Vec<LogFinding> {
+        self.patterns.iter().filter_map(|(name, re)| {
+            let hits: Vec<&String> = lines.iter().filter(|l| re.is_match(l)).collect();
+            let last = hits.last()?;
+            Some(LogFinding {
+                pattern: name.clone(),
+                count: hits.len(),
+                example: last.to_string(),
+            })
+        }).collect()
+    }
+
+    pub fn scan_all(&self, containers: &mut [ContainerInfo]) {
+        for c in containers {
+            if let Some(logs) = &c.log_tail {
+                c.log_findings = self.scan(logs);
+            }
+        }
+    }
+}
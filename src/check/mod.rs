@@ -1,27 +1,78 @@
+pub mod anonymize;
+pub mod audit;
+pub mod cgroup;
 pub mod container;
 pub mod collector;
 pub mod engine;
 pub mod events;
+pub mod filter;
 pub mod host;
+pub mod influx;
+pub mod logscan;
+pub mod oom;
+pub mod outdir;
 pub mod output;
+pub mod overview;
 pub mod report;
+pub mod seccomp;
+pub mod severity;
+pub mod timeline;
+pub mod top;
 
-use crate::utils::Result;
+// ── 详细级别（-v 可重复） ─────────────────────────────────────────────────────
+
+/// -v：完整日志、全部事件、daemon 日志、时间线与全部标签
+pub const VERBOSE_DETAIL: u8 = 1;
+/// -vv：另外逐文件列出挂载权限
+pub const VERBOSE_MOUNT_FILES: u8 = 2;
+/// -vvv：另外附带每个容器的原始 docker inspect（同 --raw-inspect）
+pub const VERBOSE_RAW: u8 = 3;
+
+use crate::cli::CheckArgs;
+use crate::utils::{Result, SedockerError};
 use report::CheckReport;
 
-pub fn run_check(container: Option<String>, output_format: &str, verbose: bool) -> Result<()> {
+pub fn run_check(args: &CheckArgs) -> Result<()> {
+    if let Some(secs) = args.wait_for_docker {
+        engine::wait_for_daemon(std::time::Duration::from_secs(secs))?;
+    }
+
+    if args.top {
+        return top::run(args);
+    }
+
+    let verbose = args.verbose >= VERBOSE_DETAIL;
+    // --pid：先把宿主机 PID 映射到容器，失败时在采集前报错
+    let target = match args.pid {
+        Some(pid) => Some(container_for_pid(pid)?),
+        None => args.container.clone(),
+    };
+    // 先编译日志特征，正则错误在采集前暴露
+    let log_scanner = logscan::LogScanner::new(&args.log_patterns)?;
+
     eprintln!("Collecting host information...");
-    let host = host::collect()?;
+    let mut warnings = Vec::new();
+    let host = host::collect(&mut warnings);
 
     eprintln!("Collecting Docker engine information...");
-    let engine = engine::collect(verbose)?;
+    let engine = engine::collect(args.verbose)?;
 
     eprintln!("Collecting container information...");
-    let containers = match container {
-        Some(ref id) => vec![collector::collect_one(id, verbose)?],
-        None         => collector::collect_all(verbose)?,
+    let collect_opts = collector::CollectOptions::from_args(args);
+    let mut skipped = Vec::new();
+    let mut containers = match target {
+        Some(ref id) => vec![collector::collect_one(id, &collect_opts)?],
+        None         => collector::collect_all(&collect_opts, &filter::ContainerFilter::from_args(args), &mut skipped)?,
     };
 
+    log_scanner.scan_all(&mut containers);
+    collector::attach_image_info(&mut containers);
+
+    if let Some(secs) = args.sample {
+        eprintln!("Sampling container stats over {}s...", secs);
+        collector::sample_rates(&mut containers, secs);
+    }
+
     eprintln!("Collecting recent events...");
     let ev = if verbose {
         events::collect(events::default_since())
@@ -29,7 +80,12 @@ pub fn run_check(container: Option<String>, output_format: &str, verbose: bool)
         events::collect_with_limit(events::default_since(), 10)
     };
 
-    let report = CheckReport {
+    timeline::attach(&mut containers, &ev);
+    let oom_kills = oom::collect(&containers, &ev);
+
+    let security_overview = overview::build(&containers);
+
+    let mut report = CheckReport {
         collected_at: chrono::Local::now()
             .format("%Y-%m-%d %H:%M:%S %z")
             .to_string(),
@@ -37,7 +93,85 @@ pub fn run_check(container: Option<String>, output_format: &str, verbose: bool)
         engine,
         containers,
         events: ev,
+        oom_kills,
+        security_overview,
+        findings: Vec::new(),
+        warnings,
+        skipped,
     };
 
-    output::display(&report, output_format, verbose)
+    let limits = audit::LimitThresholds {
+        memory_percent: args.mem_warn_percent,
+        pids_percent:   args.pids_warn_percent,
+        cpu_percent:    args.cpu_warn_percent,
+    };
+    let audit_cfg = audit::AuditConfig {
+        crash_loop_restarts:    args.crash_loop_restarts,
+        crash_loop_uptime_secs: args.crash_loop_uptime,
+        limits,
+        max_age_days: args.max_age_days,
+        owner_label: args.owner_label.clone(),
+    };
+    report.findings = audit::run(&report, &audit_cfg);
+
+    if args.anonymize {
+        report = anonymize::apply(&report)?;
+    }
+
+    let opts = output::DisplayOptions {
+        compact: args.compact,
+        verbose: args.verbose,
+        table: args.table,
+        all_labels: args.all_labels,
+        group_by_image: args.group_by_image,
+        group_by_project: args.project.is_some(),
+        only_warnings: args.only_warnings,
+        limits,
+        units: args.units,
+    };
+    // --output-dir：写入按容器拆分的文件，代替标准输出的整份报告
+    match &args.output_dir {
+        Some(dir) => {
+            let n = outdir::write(&report, std::path::Path::new(dir), args.compact)?;
+            eprintln!("Wrote {} container files, host.json, engine.json and index.json to {}", n, dir);
+        }
+        None => output::display(&report, args.output, &opts)?,
+    }
+
+    // --strict：报告照常输出，采集中被容忍的失败改为错误退出
+    if args.strict {
+        let problems = report.collection_problems();
+        if !problems.is_empty() {
+            for p in &problems {
+                eprintln!("strict: {}", p);
+            }
+            return Err(SedockerError::System(format!(
+                "{} collection problem(s) (--strict)", problems.len())));
+        }
+    }
+
+    // --fail-on：报告照常输出，最高级别达到阈值时以非零退出
+    if let (Some(threshold), Some(max)) = (args.fail_on, report.max_severity()) {
+        if max >= threshold {
+            return Err(SedockerError::System(format!(
+                "findings at severity {} (--fail-on {})", max, threshold)));
+        }
+    }
+
+    if args.follow_events {
+        eprintln!("Following Docker events (Ctrl+C to stop)...");
+        events::follow(|ev| output::display_event(ev, args.output))?;
+    }
+
+    Ok(())
+}
+
+fn container_for_pid(pid: i32) -> Result<String> {
+    if !std::path::Path::new(&format!("/proc/{}", pid)).exists() {
+        return Err(SedockerError::ProcessGone(pid));
+    }
+    let id = crate::monitor::process::get_container_id(pid)
+        .ok_or_else(|| SedockerError::System(format!("PID {} is not in a container (host process)", pid)))?;
+    eprintln!("PID {} belongs to container {}", pid, id);
+    Ok(id)
 }
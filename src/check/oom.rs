@@ -0,0 +1,119 @@
+//! OOM kill 关联：docker events 中的 oom、内核日志中的 OOM killer 记录、容器 State.OOMKilled
+
+use crate::check::container::ContainerInfo;
+use crate::check::events::DockerEvent;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// 内核日志回看范围
+const KERNEL_LOG_SINCE: &str = "24 hours ago";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OomKill {
+    pub time: String,
+    pub source: String,              // event / kernel / state
+    pub container: Option<String>,   // 容器名；None = 宿主机进程或无法归属
+    pub process: String,
+    pub pid: Option<i32>,
+    pub memcg: Option<String>,       // 内核记录的 task_memcg
+}
+
+pub fn collect(containers: &[ContainerInfo], events: &[DockerEvent]) -> Vec<OomKill> {
+    let mut kills = Vec::new();
+
+    for ev in events.iter().filter(|e| e.event_type == "container" && e.action == "oom") {
+        kills.push(OomKill {
+            time: ev.timestamp.clone(),
+            source: "event".to_string(),
+            container: Some(ev.actor_name.clone()),
+            process: String::new(),
+            pid: None,
+            memcg: None,
+        });
+    }
+
+    for mut k in kernel_oom_kills() {
+        k.container = k.memcg.as_deref().and_then(|cg| {
+            containers.iter().find(|c| cg.contains(&c.id)).map(|c| c.name.clone())
+        });
+        kills.push(k);
+    }
+
+    // State.OOMKilled 但上面两处都没有记录（事件/内核日志已滚动）
+    for c in containers.iter().filter(|c| c.oom_killed) {
+        if !kills.iter().any(|k| k.container.as_deref() == Some(c.name.as_str())) {
+            kills.push(OomKill {
+                time: c.finished_at.clone(),
+                source: "state".to_string(),
+                container: Some(c.name.clone()),
+                process: String::new(),
+                pid: None,
+                memcg: None,
+            });
+        }
+    }
+
+    kills
+}
+
+/// 解析内核 OOM killer 输出：
+///   oom-kill:constraint=CONSTRAINT_MEMCG,...,task_memcg=/system.slice/docker-<id>.scope,task=java,pid=1234,uid=0
+///   Out of memory: Killed process 1234 (java) total-vm:...
+/// 两行按 pid 合并；前者提供 cgroup，后者提供时间与进程名
+fn kernel_oom_kills() -> Vec<OomKill> {
+    let log = read_kernel_log();
+    let mut kills: Vec<OomKill> = Vec::new();
+    let mut pending_memcg: Option<(i32, String)> = None;
+
+    for line in log.lines() {
+        if let Some(pos) = line.find("oom-kill:") {
+            let fields = &line[pos + "oom-kill:".len()..];
+            let get = |key: &str| fields.split(',')
+                .find_map(|kv| kv.strip_prefix(key))
+                .map(String::from);
+            if let (Some(cg), Some(pid)) = (get("task_memcg="), get("pid=").and_then(|p| p.parse().ok())) {
+                pending_memcg = Some((pid, cg));
+            }
+        } else if let Some(pos) = line.find("Killed process ") {
+            let rest = &line[pos + "Killed process ".len()..];
+            let pid: Option<i32> = rest.split_whitespace().next().and_then(|p| p.parse().ok());
+            let process = rest.split_once('(')
+                .and_then(|(_, r)| r.split_once(')'))
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_default();
+            let memcg = match (&pending_memcg, pid) {
+                (Some((p, cg)), Some(pid)) if *p == pid => Some(cg.clone()),
+                _ => None,
+            };
+            pending_memcg = None;
+            kills.push(OomKill {
+                time: line.split_whitespace().next().unwrap_or("").to_string(),
+                source: "kernel".to_string(),
+                container: None,
+                process,
+                pid,
+                memcg,
+            });
+        }
+    }
+    kills
+}
+
+/// 优先 journalctl -k（带 ISO 时间），否则 dmesg
+fn read_kernel_log() -> String {
+    let journal = Command::new("journalctl")
+        .args(["-k", "--no-pager", "-o", "short-iso", "--since", KERNEL_LOG_SINCE])
+        .output();
+    if let Ok(o) = journal {
+        if o.status.success() && !o.stdout.is_empty() {
+            return String::from_utf8_lossy(&o.stdout).into_owned();
+        }
+    }
+    Command::new("dmesg")
+        .arg("--time-format=iso")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        .unwrap_or_default()
+}
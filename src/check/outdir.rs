@@ -0,0 +1,103 @@
+//! --output-dir：按容器拆分写 JSON 文件，便于归档与逐容器 diff
+//! <dir>/<name>.json 每容器一个，另有 host.json、engine.json 和 index.json 清单
+
+use crate::check::report::{CheckReport, SkippedContainer};
+use crate::check::severity::Finding;
+use crate::utils::{Result, SedockerError};
+use crate::utils::id::short_id;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+#[derive(Serialize)]
+struct Index<'a> {
+    collected_at: &'a str,
+    hostname: &'a str,
+    host_file: &'static str,
+    engine_file: &'static str,
+    containers: Vec<IndexEntry<'a>>,
+    findings: &'a [Finding],
+    warnings: &'a [String],
+    skipped: &'a [SkippedContainer],
+}
+
+#[derive(Serialize)]
+struct IndexEntry<'a> {
+    id: &'a str,
+    name: &'a str,
+    image: &'a str,
+    status: &'a str,
+    file: String,
+}
+
+/// 写出全部文件，返回写入的容器文件数
+pub fn write(report: &CheckReport, dir: &Path, compact: bool) -> Result<usize> {
+    std::fs::create_dir_all(dir)?;
+
+    write_json(&dir.join("host.json"), &report.host, compact)?;
+    write_json(&dir.join("engine.json"), &report.engine, compact)?;
+
+    let mut used = HashSet::new();
+    let mut entries = Vec::new();
+    for c in &report.containers {
+        let file = unique_name(&safe_filename(&c.name, &c.id), &c.id, &mut used);
+        write_json(&dir.join(&file), c, compact)?;
+        entries.push(IndexEntry {
+            id: &c.id,
+            name: &c.name,
+            image: &c.image,
+            status: &c.status,
+            file,
+        });
+    }
+
+    let count = entries.len();
+    let index = Index {
+        collected_at: &report.collected_at,
+        hostname: &report.host.os.hostname,
+        host_file: "host.json",
+        engine_file: "engine.json",
+        containers: entries,
+        findings: &report.findings,
+        warnings: &report.warnings,
+        skipped: &report.skipped,
+    };
+    write_json(&dir.join("index.json"), &index, compact)?;
+    Ok(count)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T, compact: bool) -> Result<()> {
+    let json = if compact {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+        .map_err(|e| SedockerError::System(format!("JSON serialize: {}", e)))?;
+    std::fs::write(path, json + "\n")
+        .map_err(|e| SedockerError::System(format!("write {}: {}", path.display(), e)))
+}
+
+/// 容器名转成安全文件名：只保留字母数字和 . _ -，其余替换为 _；
+/// 空名或纯点号（. / ..）退回容器 ID
+fn safe_filename(name: &str, id: &str) -> String {
+    let cleaned: String = name.trim_start_matches('/')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '_' })
+        .collect();
+    if cleaned.trim_matches('.').is_empty() {
+        short_id(id)
+    } else {
+        cleaned
+    }
+}
+
+/// 与保留文件名或已用名冲突时追加短 ID
+fn unique_name(base: &str, id: &str, used: &mut HashSet<String>) -> String {
+    let short = short_id(id);
+    let mut name = format!("{}.json", base);
+    if matches!(name.as_str(), "host.json" | "engine.json" | "index.json") || used.contains(&name) {
+        name = format!("{}-{}.json", base, short);
+    }
+    used.insert(name.clone());
+    name
+}
@@ -1,21 +1,83 @@
 //! 输出层：接收 CheckReport，渲染 text 或 json
 
 use crate::check::report::CheckReport;
-use crate::check::container::ContainerInfo;
-use crate::utils::{Result, SedockerError};
+use crate::check::{VERBOSE_DETAIL, VERBOSE_MOUNT_FILES};
+use crate::check::audit::{CONTAINER_CLOCK_SKEW_WARN_SECS, HOST_CLOCK_SKEW_WARN_MS};
+use crate::check::severity::Finding;
+use crate::check::container::{ContainerInfo, EnvSource, MacKind};
+use crate::check::events::DockerEvent;
+use crate::check::filter::COMPOSE_PROJECT_LABEL;
+use crate::utils::{OutputFormat, Result, SedockerError, Units};
+use crate::utils::time::format_duration;
+use std::collections::BTreeMap;
+
+/// PSI some/full avg10 超过该百分比时高亮
+const PSI_SOME_WARN: f64 = 10.0;
+const PSI_FULL_WARN: f64 = 5.0;
+/// oom_score（0–1000）超过该值时高亮
+const OOM_SCORE_WARN: i32 = 500;
+
+/// 渲染选项（来自 CLI）
+pub struct DisplayOptions {
+    pub compact: bool,
+    pub verbose: u8,   // -v 次数，见 check::VERBOSE_*
+    pub table: bool,
+    pub all_labels: bool,
+    pub group_by_image: bool,
+    pub group_by_project: bool,
+    pub only_warnings: bool,
+    pub limits: crate::check::audit::LimitThresholds,
+    pub units: Units,
+}
+
+/// compose 为容器打上的服务名标签
+const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
 
-pub fn display(report: &CheckReport, format: &str, verbose: bool) -> Result<()> {
+pub fn display(report: &CheckReport, format: OutputFormat, opts: &DisplayOptions) -> Result<()> {
     match format {
-        "json" => display_json(report),
-        "text" => display_text(report, verbose),
-        other  => Err(SedockerError::System(format!("unknown format: {}", other))),
+        OutputFormat::Json   => display_json(report, opts.compact),
+        OutputFormat::Jsonl  => display_json(report, true),
+        OutputFormat::Influx => {
+            print!("{}", crate::check::influx::render(report));
+            Ok(())
+        }
+        OutputFormat::Text if opts.table => display_table(report),
+        OutputFormat::Text   => display_text(report, opts),
     }
 }
 
+/// 输出单条实时事件（--follow-events）；JSON 模式下每行一个事件
+pub fn display_event(ev: &DockerEvent, format: OutputFormat) {
+    if format.is_json() {
+        if let Ok(line) = serde_json::to_string(ev) {
+            println!("{}", line);
+        }
+        return;
+    }
+
+    // OOM / die / unhealthy 高亮
+    let marker = if ev.action == "oom" {
+        "  ⚠ OOM"
+    } else if ev.action == "die" {
+        "  ⚠ DIE"
+    } else if ev.action.starts_with("health_status: unhealthy") {
+        "  ⚠ UNHEALTHY"
+    } else {
+        ""
+    };
+    println!("  {}  [{:<12}] {:<10} {}{}",
+        ev.timestamp, ev.actor_name, ev.event_type, ev.action, marker);
+}
+
 // ── JSON ────────────────────────────────────────────────────────────────────
 
-fn display_json(report: &CheckReport) -> Result<()> {
-    let json = serde_json::to_string_pretty(report)
+fn display_json(report: &CheckReport, compact: bool) -> Result<()> {
+    // compact: 单行输出，便于管道/日志存储（每行一个报告）
+    let json = if compact {
+        serde_json::to_string(report)
+    } else {
+        serde_json::to_string_pretty(report)
+    }
         .map_err(|e| SedockerError::System(format!("JSON serialize: {}", e)))?;
     println!("{}", json);
     Ok(())
@@ -23,7 +85,9 @@ fn display_json(report: &CheckReport) -> Result<()> {
 
 // ── Text ────────────────────────────────────────────────────────────────────
 
-fn display_text(report: &CheckReport, verbose: bool) -> Result<()> {
+fn display_text(report: &CheckReport, opts: &DisplayOptions) -> Result<()> {
+    let verbose = opts.verbose >= VERBOSE_DETAIL;
+    let units = opts.units;
     print_section("REPORT");
     println!("  Collected at : {}", report.collected_at);
 
@@ -34,7 +98,7 @@ fn display_text(report: &CheckReport, verbose: bool) -> Result<()> {
     println!("  OS           : {}", h.os.os_release);
     println!("  Kernel       : {}", h.os.kernel);
     println!("  Arch         : {}", h.os.arch);
-    println!("  Uptime       : {}", format_uptime(h.os.uptime_seconds));
+    println!("  Uptime       : {}", format_duration(h.os.uptime_seconds));
 
     println!("  CPU          : {} ({} cores)", h.cpu.model, h.cpu.logical_cores);
     println!("  Load avg     : {:.2}  {:.2}  {:.2}  (1/5/15 min)",
@@ -42,12 +106,21 @@ fn display_text(report: &CheckReport, verbose: bool) -> Result<()> {
 
     let m = &h.memory;
     println!("  Memory       : {} used / {} total  ({:.1}%)",
-        fmt_kb(m.used_kb), fmt_kb(m.total_kb), m.used_percent);
+        fmt_kb(units, m.used_kb), fmt_kb(units, m.total_kb), m.used_percent);
     if m.swap_total_kb > 0 {
-        println!("  Swap         : {} used / {}", fmt_kb(m.swap_used_kb), fmt_kb(m.swap_total_kb));
+        println!("  Swap         : {} used / {}", fmt_kb(units, m.swap_used_kb), fmt_kb(units, m.swap_total_kb));
     } else {
         println!("  Swap         : disabled");
     }
+    match &h.pressure {
+        Some(p) => {
+            let warn = if p.some_avg10 >= PSI_SOME_WARN || p.full_avg10 >= PSI_FULL_WARN { " ⚠" } else { "" };
+            println!("  Mem pressure : some {:.2}/{:.2}/{:.2}  full {:.2}/{:.2}/{:.2}  (avg10/60/300){}",
+                p.some_avg10, p.some_avg60, p.some_avg300,
+                p.full_avg10, p.full_avg60, p.full_avg300, warn);
+        }
+        None => println!("  Mem pressure : unavailable (no PSI)"),
+    }
 
     if !h.disk.is_empty() {
         println!("  Disk:");
@@ -59,10 +132,62 @@ fn display_text(report: &CheckReport, verbose: bool) -> Result<()> {
     }
 
     println!("  cgroup       : {}", h.cgroup_version);
+
+    if !h.os.kernel_cmdline.is_empty() || !h.sysctls.is_empty() {
+        println!("  Kernel:");
+        if !h.os.kernel_cmdline.is_empty() {
+            println!("    cmdline    : {}", h.os.kernel_cmdline);
+        }
+        for (k, v) in &h.sysctls {
+            let warn = if k == "net.ipv4.ip_forward" && v == "0" { "  ⚠ bridge networking needs forwarding" } else { "" };
+            println!("    {} = {}{}", k, v, warn);
+        }
+    }
+    let ino = &h.inotify;
+    println!("  inotify      : {} instances  {} watches  (per-user limits: {} instances / {} watches)",
+        ino.total_instances, ino.total_watches, ino.max_user_instances, ino.max_user_watches);
+    if let Some(uid) = ino.top_uid {
+        println!("                 busiest uid {}: {} instances  {} watches",
+            uid, ino.top_uid_instances, ino.top_uid_watches);
+    }
+    if ino.fanotify_groups > 0 {
+        match ino.fanotify_max_user_groups {
+            Some(max) => println!("  fanotify     : {} groups  (per-user limit {})", ino.fanotify_groups, max),
+            None      => println!("  fanotify     : {} groups", ino.fanotify_groups),
+        }
+    }
     println!("  SELinux      : {}", h.security.selinux);
     println!("  AppArmor     : {}", h.security.apparmor);
-    println!("  Time         : {}  NTP synced: {}", h.time.system_time,
-        if h.time.ntp_synced { "yes" } else { "no ⚠" });
+    let offset = match h.time.ntp_offset_ms {
+        Some(ms) => {
+            let warn = if ms.abs() >= HOST_CLOCK_SKEW_WARN_MS { " ⚠" } else { "" };
+            format!("  offset {:+.1}ms{}", ms, warn)
+        }
+        None => String::new(),
+    };
+    println!("  Time         : {}  NTP synced: {}{}", h.time.system_time,
+        if h.time.ntp_synced { "yes" } else { "no ⚠" }, offset);
+    // 容器时钟：只列出偏差超过阈值的容器
+    let clocks: Vec<(&str, &crate::check::container::ContainerClock)> = report.containers.iter()
+        .filter_map(|c| c.clock.as_ref().map(|k| (c.name.as_str(), k)))
+        .collect();
+    if !clocks.is_empty() {
+        let max = clocks.iter().map(|(_, k)| k.skew_seconds.abs()).max().unwrap_or(0);
+        println!("  Container clk: {} checked, max skew {}s", clocks.len(), max);
+        for (name, k) in clocks.iter().filter(|(_, k)| k.skew_seconds.abs() >= CONTAINER_CLOCK_SKEW_WARN_SECS) {
+            println!("                 ⚠ {} {:+}s (UTC{})", name, k.skew_seconds, k.utc_offset);
+        }
+    }
+    if let Some(sock) = &h.docker_socket {
+        let warn = if sock.mode & 0o006 != 0 { "  ⚠ world-accessible" } else { "" };
+        println!("  Docker sock  : {}  {}:{}  {:o}{}", sock.path, sock.owner, sock.group, sock.mode, warn);
+    }
+    if !h.docker_group.is_empty() {
+        let members: Vec<String> = h.docker_group.iter()
+            .map(|m| if m.interactive { format!("{} ⚠", m.name) } else { m.name.clone() })
+            .collect();
+        println!("  docker group : {}", members.join(", "));
+    }
 
     // ── Engine ────────────────────────────────────────────────────────────
     print_section("DOCKER ENGINE");
@@ -77,6 +202,15 @@ fn display_text(report: &CheckReport, verbose: bool) -> Result<()> {
     println!("  cgroup ver   : {}", e.runtime.cgroup_version);
     println!("  Log driver   : {}", e.runtime.log_driver);
     println!("  Root dir     : {}", e.runtime.root_dir);
+    if let Some(st) = &e.storage {
+        let warn = if st.used_percent > 85.0 { "  ⚠" } else { "" };
+        println!("  Docker stor  : {} on {} ({})  used {} / {} ({:.0}%){}",
+            st.root_dir, st.mount, st.filesystem,
+            fmt_kb(units, st.used_kb), fmt_kb(units, st.total_kb), st.used_percent, warn);
+        if let Some(kb) = st.driver_used_kb {
+            println!("                 {} : {}", st.driver_dir, fmt_kb(units, kb));
+        }
+    }
     println!("  Containers   : {} total  {} running  {} paused  {} stopped",
         e.runtime.total_containers, e.runtime.running_containers,
         e.runtime.paused_containers, e.runtime.stopped_containers);
@@ -99,10 +233,103 @@ fn display_text(report: &CheckReport, verbose: bool) -> Result<()> {
     }
 
     // ── Containers ────────────────────────────────────────────────────────
-    print_section(&format!("CONTAINERS ({})", report.containers.len()));
-    for (i, c) in report.containers.iter().enumerate() {
-        println!("  [{}/{}]", i + 1, report.containers.len());
-        display_container_text(c, verbose);
+    // --only-warnings：只保留有 finding 的容器，finding 随容器内联输出
+    let shown: Vec<&ContainerInfo> = report.containers.iter()
+        .filter(|c| !opts.only_warnings
+            || report.findings.iter().any(|f| f.container.as_deref() == Some(c.name.as_str())))
+        .collect();
+    if opts.only_warnings {
+        print_section(&format!("CONTAINERS ({} of {} flagged)", shown.len(), report.containers.len()));
+    } else {
+        print_section(&format!("CONTAINERS ({})", report.containers.len()));
+    }
+    let inline = if opts.only_warnings { report.findings.as_slice() } else { &[] };
+    if opts.group_by_project {
+        let groups = group_containers(&shown, |c| {
+            c.labels.get(COMPOSE_PROJECT_LABEL).cloned().unwrap_or_else(|| "(none)".to_string())
+        });
+        display_container_groups("PROJECT", &groups, inline, opts);
+    } else if opts.group_by_image {
+        let groups = group_containers(&shown, |c| c.image.clone());
+        display_container_groups("IMAGE", &groups, inline, opts);
+    } else {
+        for (i, c) in shown.iter().enumerate() {
+            println!("  [{}/{}]", i + 1, shown.len());
+            display_container_text(c, opts);
+            display_inline_findings(c, inline);
+        }
+    }
+
+    // ── Security overview ─────────────────────────────────────────────────
+    let so = &report.security_overview;
+    if so.total > 0 {
+        print_section("SECURITY OVERVIEW");
+        let pct = |n: usize| n as f64 * 100.0 / so.total as f64;
+        let rows = [
+            ("Privileged",      so.privileged),
+            ("Default caps",    so.default_caps),
+            ("Caps dropped",    so.reduced_caps),
+            ("Dangerous caps",  so.dangerous_caps),
+            ("No new privs",    so.no_new_privileges),
+            ("RO rootfs",       so.read_only_rootfs),
+        ];
+        for (label, n) in rows {
+            println!("  {:<16}: {:>3} / {}  ({:.0}%)", label, n, so.total, pct(n));
+        }
+        if !so.dangerous_cap_counts.is_empty() {
+            let caps: Vec<String> = so.dangerous_cap_counts.iter()
+                .map(|(cap, n)| format!("{} ×{}", cap, n))
+                .collect();
+            println!("  {:<16}: {}", "Added", caps.join(", "));
+        }
+    }
+
+    // ── Findings ──────────────────────────────────────────────────────────
+    // --only-warnings 时容器 finding 已内联，这里只剩宿主机/引擎层
+    let listed: Vec<_> = report.findings.iter()
+        .filter(|f| !opts.only_warnings || f.container.is_none())
+        .collect();
+    if !listed.is_empty() {
+        print_section(&format!("FINDINGS ({})", listed.len()));
+        for f in listed {
+            println!("  ⚠ {:<8} [{}] {}  ({})  owner: {}",
+                f.severity.as_str().to_uppercase(), f.container.as_deref().unwrap_or("host"), f.title, f.id,
+                f.owner.as_deref().unwrap_or("unassigned"));
+            for line in f.detail.lines() {
+                println!("      {}", line);
+            }
+        }
+    }
+
+    // ── OOM ───────────────────────────────────────────────────────────────
+    if !report.oom_kills.is_empty() {
+        print_section(&format!("OOM KILLS ({})", report.oom_kills.len()));
+        for k in &report.oom_kills {
+            let process = match k.pid {
+                Some(pid) => format!("{} (pid {})", k.process, pid),
+                None if k.process.is_empty() => "-".to_string(),
+                None => k.process.clone(),
+            };
+            println!("  ⚠ {}  [{:<6}] {:<20} {}",
+                k.time, k.source, k.container.as_deref().unwrap_or("host/unknown"), process);
+        }
+    }
+
+    // ── Warnings ──────────────────────────────────────────────────────────
+    if !report.warnings.is_empty() {
+        print_section(&format!("COLLECTION WARNINGS ({})", report.warnings.len()));
+        for w in &report.warnings {
+            println!("  ⚠ {}", w);
+        }
+    }
+
+    // ── Skipped ───────────────────────────────────────────────────────────
+    // 区分"主机只有 N 个容器"与"有更多但读不到"
+    if !report.skipped.is_empty() {
+        print_section(&format!("SKIPPED ({})", report.skipped.len()));
+        for s in &report.skipped {
+            println!("  ⚠ {:<12}  {}", s.id, s.reason);
+        }
     }
 
     // ── Events ────────────────────────────────────────────────────────────
@@ -123,7 +350,178 @@ fn display_text(report: &CheckReport, verbose: bool) -> Result<()> {
     Ok(())
 }
 
-fn display_container_text(c: &ContainerInfo, verbose: bool) {
+// ── 分组 ────────────────────────────────────────────────────────────────────
+
+/// 按 key 分组（BTreeMap 保证输出顺序稳定）
+fn group_containers<'a, F>(containers: &[&'a ContainerInfo], key: F) -> BTreeMap<String, Vec<&'a ContainerInfo>>
+where
+    F: Fn(&ContainerInfo) -> String,
+{
+    let mut groups: BTreeMap<String, Vec<&ContainerInfo>> = BTreeMap::new();
+    for &c in containers {
+        groups.entry(key(c)).or_default().push(c);
+    }
+    groups
+}
+
+/// 每组先输出汇总行（数量、运行数、CPU/内存合计），再输出各容器详情
+fn display_container_groups(label: &str, groups: &BTreeMap<String, Vec<&ContainerInfo>>, inline: &[Finding], opts: &DisplayOptions) {
+    let units = opts.units;
+    for (name, members) in groups {
+        let running = members.iter().filter(|c| c.status == "running").count();
+        let cpu: f64 = members.iter()
+            .filter_map(|c| c.resource_usage.as_ref())
+            .map(|u| u.cpu_percent)
+            .sum();
+        let mem: u64 = members.iter()
+            .filter_map(|c| c.resource_usage.as_ref())
+            .map(|u| u.memory_usage)
+            .sum();
+
+        println!("  ▸ {} {}  ({} containers, {} running)  CPU {:.2}%  MEM {}",
+            label, name, members.len(), running, cpu, units.format_bytes(mem));
+        if opts.group_by_project {
+            display_service_rollup(members);
+        }
+        for (i, c) in members.iter().enumerate() {
+            println!("  [{}/{}]", i + 1, members.len());
+            display_container_text(c, opts);
+            display_inline_findings(c, inline);
+        }
+    }
+}
+
+/// 容器详情之后列出属于它的 findings（--only-warnings）
+fn display_inline_findings(c: &ContainerInfo, findings: &[Finding]) {
+    let own: Vec<&Finding> = findings.iter()
+        .filter(|f| f.container.as_deref() == Some(c.name.as_str()))
+        .collect();
+    if own.is_empty() {
+        return;
+    }
+    println!("      Findings:");
+    for f in own {
+        println!("        ⚠ {:<8} {}  ({})  owner: {}", f.severity.as_str().to_uppercase(), f.title, f.id,
+            f.owner.as_deref().unwrap_or("unassigned"));
+        for line in f.detail.lines() {
+            println!("            {}", line);
+        }
+    }
+}
+
+/// compose 服务状态汇总：服务全部副本运行为 up，否则为 down
+fn display_service_rollup(members: &[&ContainerInfo]) {
+    let mut services: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for c in members {
+        let svc = c.labels.get(COMPOSE_SERVICE_LABEL).map(String::as_str).unwrap_or(c.name.as_str());
+        let entry = services.entry(svc).or_default();
+        entry.1 += 1;
+        if c.status == "running" {
+            entry.0 += 1;
+        }
+    }
+    let (up, down): (Vec<_>, Vec<_>) = services.iter().partition(|(_, (r, t))| r == t);
+    let names = |v: &[(&&str, &(usize, usize))]| {
+        v.iter().map(|(s, (r, t))| format!("{} {}/{}", s, r, t)).collect::<Vec<_>>().join(", ")
+    };
+    println!("    Services up   ({}) : {}", up.len(), names(&up));
+    if !down.is_empty() {
+        println!("    ⚠ Services down ({}) : {}", down.len(), names(&down));
+    }
+}
+
+// ── Table ───────────────────────────────────────────────────────────────────
+
+/// IMAGE 列最大宽度，超出部分以省略号截断
+const TABLE_IMAGE_MAX: usize = 40;
+
+fn display_table(report: &CheckReport) -> Result<()> {
+    let headers = ["NAME", "STATUS", "IMAGE", "CPU%", "MEM%", "PRIV", "RESTARTS"];
+
+    let rows: Vec<[String; 7]> = report.containers.iter().map(|c| {
+        let (cpu, mem) = match &c.resource_usage {
+            Some(u) => (format!("{:.2}", u.cpu_percent), format!("{:.1}", u.memory_percent)),
+            None    => ("-".to_string(), "-".to_string()),
+        };
+        [
+            c.name.clone(),
+            c.status.clone(),
+            truncate_ellipsis(&c.image, TABLE_IMAGE_MAX),
+            cpu,
+            mem,
+            if c.security.privileged { "yes ⚠".to_string() } else { "no".to_string() },
+            c.restart_count.to_string(),
+        ]
+    }).collect();
+
+    print_aligned(&headers, &rows);
+    Ok(())
+}
+
+/// 按字符数计算列宽对齐输出（⚠ 等非 ASCII 字符按 1 列计）
+fn print_aligned<R: AsRef<[String]>>(headers: &[&str], rows: &[R]) {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (w, cell) in widths.iter_mut().zip(row.as_ref().iter()) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+
+    let render = |cells: &[String]| -> String {
+        cells.iter().zip(&widths)
+            .map(|(cell, w)| format!("{:<w$}", cell, w = *w))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let header_cells: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    println!("{}", render(&header_cells));
+    for row in rows {
+        println!("{}", render(row.as_ref()));
+    }
+}
+
+/// --top 刷新一帧：清屏后输出排好序的资源表
+pub fn display_top(rows: &[crate::check::top::TopRow], sort: &str, interval: u64, units: Units) {
+    print!("\x1b[2J\x1b[H");
+    println!("sedock top — {}  sorted by {}  every {}s  (Ctrl+C to exit)\n",
+        chrono::Local::now().format("%H:%M:%S"), sort, interval);
+
+    let headers = ["NAME", "ID", "CPU%", "MEM", "MEM%", "NET rx/tx", "BLK r/w", "PIDS", "PRIV"];
+    let cells: Vec<[String; 9]> = rows.iter().map(|r| {
+        let u = &r.usage;
+        [
+            r.name.clone(),
+            r.id.clone(),
+            format!("{:.2}", u.cpu_percent),
+            format!("{} / {}", units.format_bytes(u.memory_usage), units.format_bytes(u.memory_limit)),
+            format!("{:.1}", u.memory_percent),
+            format!("{} / {}", units.format_bytes(u.net_rx), units.format_bytes(u.net_tx)),
+            format!("{} / {}", units.format_bytes(u.block_read), units.format_bytes(u.block_write)),
+            u.pids.to_string(),
+            if r.privileged { "yes ⚠".to_string() } else { "no".to_string() },
+        ]
+    }).collect();
+    print_aligned(&headers, &cells);
+}
+
+fn truncate_ellipsis(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        let head: String = s.chars().take(max.saturating_sub(1)).collect();
+        format!("{}…", head)
+    }
+}
+
+/// 非 verbose 模式下默认隐藏的系统标签前缀
+const SYSTEM_LABEL_PREFIXES: &[&str] = &["com.docker.", "org.opencontainers."];
+
+fn display_container_text(c: &ContainerInfo, opts: &DisplayOptions) {
+    let verbose = opts.verbose >= VERBOSE_DETAIL;
+    let units = opts.units;
     let status_icon = match c.status.as_str() {
         "running" => "●",
         "exited"  => "○",
@@ -137,16 +535,44 @@ fn display_container_text(c: &ContainerInfo, verbose: bool) {
         String::new()
     };
 
-    println!("  {} {} [{}]{}",
-        status_icon, c.name, c.status, exit_info);
+    let status_age = if c.status_age.is_empty() { String::new() } else { format!(" ({})", c.status_age) };
+    println!("  {} {} [{}{}]{}",
+        status_icon, c.name, c.status, status_age, exit_info);
     println!("      ID         : {}", c.id);
-    println!("      Image      : {}  ({})", c.image, c.image_id);
-    println!("      Created    : {}", c.created);
+    match c.image_age_seconds {
+        Some(age) => println!("      Image      : {}  ({}, built {} ago)", c.image, c.image_id, format_duration(age)),
+        None      => println!("      Image      : {}  ({})", c.image, c.image_id),
+    }
+    match c.age_seconds {
+        Some(age) => println!("      Created    : {}  ({} ago)", c.created, format_duration(age)),
+        None      => println!("      Created    : {}", c.created),
+    }
     println!("      Started    : {}", c.started_at);
     if c.status != "running" {
         println!("      Finished   : {}", c.finished_at);
     }
-    println!("      Restart    : {}  (count: {})", c.restart_policy, c.restart_count);
+    println!("      Restart    : {}  (count: {}){}", c.restart_policy, c.restart_count,
+        if c.auto_remove { "  auto-remove (--rm)" } else { "" });
+    if let Some(d) = &c.last_death {
+        let ago = (chrono::Utc::now() - d.finished_at).num_seconds().max(0) as u64;
+        let label = crate::check::timeline::death_label(d)
+            .map(|l| format!(" ({})", l))
+            .unwrap_or_default();
+        println!("      Last died  : {} ago, exit {}{}", format_duration(ago), d.exit_code, label);
+    }
+    if let Some(health) = &c.lifecycle.health_status {
+        let warn = if health == "unhealthy" { "  ⚠" } else { "" };
+        println!("      Health     : {}{}", health, warn);
+    }
+    match &c.healthcheck_config {
+        Some(hc) => {
+            let warn = if hc.timeout_secs >= hc.interval_secs { "  ⚠ timeout ≥ interval" } else { "" };
+            println!("      Healthchk  : {}", hc.test.join(" "));
+            println!("                   every {}s, timeout {}s, {} retries, start period {}s{}",
+                hc.interval_secs, hc.timeout_secs, hc.retries, hc.start_period_secs, warn);
+        }
+        None => println!("      Healthchk  : none"),
+    }
     println!("      Entrypoint : {}", if c.entrypoint.is_empty() { "(none)" } else { &c.entrypoint });
     println!("      Cmd        : {}", if c.cmd.is_empty() { "(none)" } else { &c.cmd });
     println!("      Path       : {}", if c.path.is_empty() { "(none)" } else { &c.path });
@@ -155,6 +581,22 @@ fn display_container_text(c: &ContainerInfo, verbose: bool) {
         println!("      Work dir   : {}", c.working_dir);
     }
 
+    // ── Labels ────────────────────────────────────────────────────────────
+    if !c.labels.is_empty() {
+        let show_all = verbose || opts.all_labels;
+        let shown: Vec<(&String, &String)> = c.labels.iter()
+            .filter(|(k, _)| show_all || !SYSTEM_LABEL_PREFIXES.iter().any(|p| k.starts_with(p)))
+            .collect();
+        let hidden = c.labels.len() - shown.len();
+        println!("      Labels:");
+        for (k, v) in &shown {
+            println!("        {}={}", k, v);
+        }
+        if hidden > 0 {
+            println!("        ({} system labels hidden, use --all-labels)", hidden);
+        }
+    }
+
     // ── User ──────────────────────────────────────────────────────────────
     if !c.user.is_empty() {
         println!("      User       : {}", c.user);
@@ -206,11 +648,11 @@ fn display_container_text(c: &ContainerInfo, verbose: bool) {
     }
 
     // ── Security ──────────────────────────────────────────────────────────
-    display_security_section(&c.security);
+    display_security_section(&c.security, &c.network_mode);
 
     // ── Processes ─────────────────────────────────────────────────────────
     if !c.processes.is_empty() {
-        println!("      Processes  :");
+        println!("      Processes  : (PID host/container)");
         for p in &c.processes {
             let exe_info = p.exe_path.as_ref()
                 .map(|path| format!(" → {}", path))
@@ -219,8 +661,24 @@ fn display_container_text(c: &ContainerInfo, verbose: bool) {
                 .map(|cwd| format!(" (cwd: {})", cwd))
                 .unwrap_or_default();
 
+            // PID 宿主机/容器
+            let pid = match p.container_pid {
+                Some(cpid) => format!("{}/{}", p.pid, cpid),
+                None => p.pid.to_string(),
+            };
             println!("        PID {} (PPID {})  {}:{}  {}{}{}",
-                p.pid, p.ppid, p.uid, p.gid, p.cmd, exe_info, cwd_info);
+                pid, p.ppid, p.uid, p.gid, p.cmd, exe_info, cwd_info);
+        }
+
+        // 主进程（父进程不在容器内）的 cwd 与声明的 WorkingDir 不一致时提示，
+        // 通常意味着 entrypoint 包装脚本 cd 到了别处
+        let main = c.processes.iter()
+            .find(|p| !c.processes.iter().any(|q| q.pid == p.ppid));
+        if let Some(cwd) = main.and_then(|p| p.cwd.as_deref()) {
+            let declared = if c.working_dir.is_empty() { "/" } else { c.working_dir.as_str() };
+            if cwd.trim_end_matches('/') != declared.trim_end_matches('/') {
+                println!("        ⚠ main process cwd {} differs from WorkingDir {}", cwd, declared);
+            }
         }
     }
 
@@ -231,6 +689,13 @@ fn display_container_text(c: &ContainerInfo, verbose: bool) {
             println!("        {}:{} -> {}/{}", p.host_ip, p.host_port, p.container_port, p.protocol);
         }
     }
+    if !c.exposed_ports.is_empty() {
+        println!("      Exposed:");
+        for spec in &c.exposed_ports {
+            let reach = if crate::check::audit::is_published(c, spec) { "published" } else { "network-internal" };
+            println!("        {:<12} {}", spec, reach);
+        }
+    }
 
     if !c.networks.is_empty() {
         println!("      Networks:");
@@ -245,15 +710,31 @@ fn display_container_text(c: &ContainerInfo, verbose: bool) {
     if !c.mounts.is_empty() {
         println!("      Mounts:");
         for m in &c.mounts {
-            println!("        [{}] {} → {}  {} {}",
+            let warn = if m.mount_type != "bind" {
+                ""
+            } else if m.source_type == "missing" {
+                "  ⚠ source missing"
+            } else if m.source_type == "dir" && crate::check::audit::looks_like_file(&m.destination) {
+                "  ⚠ source is a directory"
+            } else {
+                ""
+            };
+            println!("        [{}] {} → {}  {} {}{}",
                 m.mount_type, m.source, m.destination, m.mode,
-                if m.rw { "rw" } else { "ro" });
+                if m.rw { "rw" } else { "ro" }, warn);
+            if let Some(t) = &m.tmpfs {
+                let size = t.size_bytes.map(|b| units.format_bytes(b)).unwrap_or_else(|| "unset (half of RAM)".to_string());
+                let warn = if t.exec && t.suid { "  ⚠ exec+suid" } else { "" };
+                println!("          tmpfs size: {}  {}  {}{}", size,
+                    if t.exec { "exec" } else { "noexec" },
+                    if t.suid { "suid" } else { "nosuid" }, warn);
+            }
 
             if !m.permissions.is_empty() {
                 // Always show compact summary
                 display_mount_permissions_summary(&m.permissions);
-                // Verbose: also show full per-file listing
-                if verbose {
+                // -vv: also show full per-file listing
+                if opts.verbose >= VERBOSE_MOUNT_FILES {
                     println!("          Details (mode uid:gid path):");
                     for p in &m.permissions {
                         println!("            {:o} {}:{} {}",
@@ -264,30 +745,83 @@ fn display_container_text(c: &ContainerInfo, verbose: bool) {
         }
     }
 
+    // ── FS changes ────────────────────────────────────────────────────────
+    if !c.fs_changes.is_empty() {
+        display_fs_changes(&c.fs_changes, verbose);
+    }
+
     // ── Resources ─────────────────────────────────────────────────────────
     let rc = &c.resource_config;
     let mem_lim = if rc.memory_limit == 0 {
         "unlimited".to_string()
     } else {
-        fmt_bytes(rc.memory_limit)
+        units.format_bytes(rc.memory_limit)
     };
     println!("      Res config : cpu_shares={}  cpu_quota={}  mem_limit={}  pids={}",
         rc.cpu_shares, rc.cpu_quota, mem_lim, rc.pids_limit);
+    println!("      Swap limit : {}", fmt_swap_limit(rc, units));
+    if rc.oom_kill_disable {
+        let warn = if rc.memory_limit == 0 { "  ⚠ no memory limit, host may hang" } else { "" };
+        println!("      OOM kill   : disabled{}", warn);
+    }
 
     if let Some(u) = &c.resource_usage {
-        println!("      Res usage  : CPU {:.2}%  MEM {} / {} ({:.1}%)  PIDs {}",
+        let breakdown = u.memory_breakdown.as_ref()
+            .map(|m| format!(" (rss {}, cache {})", units.format_bytes(m.rss), units.format_bytes(m.cache)))
+            .unwrap_or_default();
+        println!("      Res usage  : CPU {:.2}%  MEM {}{} / {} ({:.1}%)  PIDs {}",
             u.cpu_percent,
-            fmt_bytes(u.memory_usage), fmt_bytes(u.memory_limit),
+            units.format_bytes(u.memory_usage), breakdown, units.format_bytes(u.memory_limit),
             u.memory_percent, u.pids);
+        for b in crate::check::audit::limit_breaches(c, &opts.limits) {
+            println!("        ⚠ {} at {:.1}% of limit", b.resource, b.used_percent);
+        }
         println!("                   Net rx={} tx={}  Blk r={} w={}",
-            fmt_bytes(u.net_rx), fmt_bytes(u.net_tx),
-            fmt_bytes(u.block_read), fmt_bytes(u.block_write));
+            units.format_bytes(u.net_rx), units.format_bytes(u.net_tx),
+            units.format_bytes(u.block_read), units.format_bytes(u.block_write));
+        if let Some(secs) = u.sample_secs {
+            // 计数器读不到时为 "?"，不显示成 0
+            let rate = |r: Option<f64>| r.map_or("?".to_string(), |v| format!("{}/s", units.format_bytes(v as u64)));
+            println!("      Rates ({}s) : Net rx={} tx={}  Blk r={} w={}",
+                secs,
+                rate(u.net_rx_rate), rate(u.net_tx_rate),
+                rate(u.block_read_rate), rate(u.block_write_rate));
+        }
+    }
+    if let Some(score) = c.oom_score {
+        let warn = if score >= OOM_SCORE_WARN { " ⚠" } else { "" };
+        println!("      OOM score  : {}  (adj {}){}",
+            score, c.oom_score_adj.map(|a| a.to_string()).unwrap_or_else(|| "?".to_string()), warn);
     }
 
     if !c.env.is_empty() {
         println!("      Env:");
         for e in &c.env {
-            println!("        {}", e);
+            // 运行时注入的变量（含 --env-file）单独标出，便于审计
+            let key = e.split_once('=').map(|(k, _)| k).unwrap_or(e);
+            let tag = match c.env_source.get(key) {
+                Some(EnvSource::Image)      => "  [image]",
+                Some(EnvSource::Overridden) => "  [run: overrides image]",
+                Some(EnvSource::Added)      => "  [run]",
+                None                        => "",
+            };
+            println!("        {}{}", e, tag);
+        }
+    }
+
+    if verbose && !c.timeline.is_empty() {
+        println!("      Timeline:");
+        for t in &c.timeline {
+            println!("        {}  [{:<6}] {}",
+                t.time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"), t.source, t.label);
+        }
+    }
+
+    // 日志特征汇总（在原始日志之前）
+    if !c.log_findings.is_empty() {
+        println!("      Log signals:");
+        for f in &c.log_findings {
+            println!("        ⚠ {:<20} ×{:<5} {}", f.pattern, f.count, truncate_ellipsis(&f.example, 100));
         }
     }
 
@@ -301,40 +835,123 @@ fn display_container_text(c: &ContainerInfo, verbose: bool) {
                 &logs[start..]
             };
             println!("      Logs (last {}):", display_logs.len());
+            if c.log_truncated && display_logs.len() == logs.len() {
+                println!("        ... (earlier logs truncated)");
+            }
             for line in display_logs {
                 println!("        {}", line);
             }
         }
     }
 
+    // 原始 inspect（-vvv / --raw-inspect）
+    if let Some(raw) = &c.raw {
+        println!("      Raw inspect:");
+        if let Ok(pretty) = serde_json::to_string_pretty(raw) {
+            for line in pretty.lines() {
+                println!("        {}", line);
+            }
+        }
+    }
+
     println!();
 }
 
+/// docker diff 汇总；系统目录下的新增/修改标 ⚠
+fn display_fs_changes(changes: &[crate::check::container::FsChange], verbose: bool) {
+    use crate::check::container::FsChangeKind;
+
+    let count = |k: FsChangeKind| changes.iter().filter(|f| f.kind == k).count();
+    let system = changes.iter()
+        .filter(|f| f.kind != FsChangeKind::Deleted && crate::check::audit::is_system_path(&f.path))
+        .count();
+    let warn = if system > 0 { format!("  ⚠ {} in system dirs", system) } else { String::new() };
+    println!("      FS changes : {} added, {} changed, {} deleted{}",
+        count(FsChangeKind::Added), count(FsChangeKind::Changed), count(FsChangeKind::Deleted), warn);
+
+    if verbose {
+        for f in changes {
+            let tag = match f.kind {
+                FsChangeKind::Added   => 'A',
+                FsChangeKind::Changed => 'C',
+                FsChangeKind::Deleted => 'D',
+            };
+            println!("        {} {}", tag, f.path);
+        }
+    }
+}
+
 /// Dedicated security section — always shown
-fn display_security_section(sec: &crate::check::container::SecurityConfig) {
+fn display_security_section(sec: &crate::check::container::SecurityConfig, network_mode: &str) {
     println!("      Security   :");
     if sec.privileged {
         println!("        ⚠ PRIVILEGED MODE");
     } else {
         println!("        Privileged  : no");
     }
+    let shared: Vec<&str> = [("net", network_mode), ("pid", sec.pid_mode.as_str()), ("ipc", sec.ipc_mode.as_str())]
+        .iter()
+        .filter(|(_, mode)| *mode == "host")
+        .map(|(ns, _)| *ns)
+        .collect();
+    if !shared.is_empty() {
+        println!("        ⚠ HOST NAMESPACES : {}", shared.join(", "));
+    }
     if !sec.capabilities.is_empty() {
         println!("        Cap added   : {}", sec.capabilities.join(", "));
     } else {
         println!("        Cap added   : (none)");
     }
+    if !sec.cap_drop.is_empty() {
+        println!("        Cap dropped : {}", sec.cap_drop.join(", "));
+    }
     if sec.seccomp_profile.is_empty() || sec.seccomp_profile == "default" {
         println!("        Seccomp     : default");
     } else {
         println!("        Seccomp     : {}", sec.seccomp_profile);
     }
+    if let Some(sum) = &sec.seccomp_summary {
+        let allowed = sum.allowed_syscalls.map(|n| n.to_string()).unwrap_or_else(|| "all not denied".to_string());
+        println!("          default {}  allowed {}", sum.default_action, allowed);
+        if !sum.dangerous_allowed.is_empty() {
+            println!("          ⚠ permits {}", sum.dangerous_allowed.join(", "));
+        }
+    }
     if sec.apparmor_profile.is_empty() || sec.apparmor_profile == "unconfined" {
         println!("        AppArmor    : unconfined");
     } else {
         println!("        AppArmor    : {}", sec.apparmor_profile);
     }
+    if !sec.applied_apparmor_profile.is_empty() && sec.applied_apparmor_profile != sec.apparmor_profile {
+        println!("        AppArmor (applied) : {}", sec.applied_apparmor_profile);
+    }
+    if let Some(label) = &sec.effective_apparmor {
+        display_effective_mac("AppArmor", label, MacKind::of_apparmor(label));
+    }
+    if !sec.process_label.is_empty() {
+        println!("        SELinux     : {}", sec.process_label);
+        println!("        Mount label : {}", sec.mount_label);
+    }
+    if let Some(context) = &sec.effective_selinux {
+        display_effective_mac("SELinux", context, MacKind::of_selinux(context));
+    }
     println!("        RO rootfs   : {}", if sec.read_only_rootfs { "yes" } else { "no" });
     println!("        No new priv : {}", if sec.no_new_privileges { "yes" } else { "no" });
+    if !sec.group_add.is_empty() {
+        let groups: Vec<String> = sec.group_add.iter()
+            .map(|g| match crate::check::audit::sensitive_group_name(g) {
+                Some(_) => format!("{} ⚠", g),
+                None    => g.clone(),
+            })
+            .collect();
+        println!("        Group add   : {}", groups.join(", "));
+    }
+}
+
+/// 主进程实际生效的 MAC 标签，附类别
+fn display_effective_mac(lsm: &str, label: &str, kind: MacKind) {
+    let warn = if kind == MacKind::Unconfined { " ⚠" } else { "" };
+    println!("        {} (effective) : {} [{}]{}", lsm, label, kind.as_str(), warn);
 }
 
 /// Compact mount permission summary — shown in both normal and verbose modes
@@ -383,37 +1000,20 @@ fn print_section(title: &str) {
     println!("{}", "─".repeat(60));
 }
 
-fn fmt_kb(kb: u64) -> String {
-    if kb >= 1024 * 1024 {
-        format!("{:.1}GiB", kb as f64 / 1024.0 / 1024.0)
-    } else if kb >= 1024 {
-        format!("{:.1}MiB", kb as f64 / 1024.0)
-    } else {
-        format!("{}KiB", kb)
-    }
+/// /proc 与 df 以 KiB 计数，统一转成字节再按 --units 格式化
+fn fmt_kb(units: Units, kb: u64) -> String {
+    units.format_bytes(kb.saturating_mul(1024))
 }
 
-fn fmt_bytes(b: u64) -> String {
-    if b >= 1 << 30 {
-        format!("{:.1}GiB", b as f64 / (1u64 << 30) as f64)
-    } else if b >= 1 << 20 {
-        format!("{:.1}MiB", b as f64 / (1u64 << 20) as f64)
-    } else if b >= 1 << 10 {
-        format!("{:.1}KiB", b as f64 / (1u64 << 10) as f64)
-    } else {
-        format!("{}B", b)
+/// MemorySwap 为 memory+swap 总量：-1 不限；0 或等于 memory 表示禁用 swap
+fn fmt_swap_limit(rc: &crate::check::container::ResourceConfig, units: Units) -> String {
+    if rc.memory_limit == 0 {
+        return "unlimited (no memory limit)".to_string();
     }
-}
-
-fn format_uptime(seconds: u64) -> String {
-    let d = seconds / 86400;
-    let h = (seconds % 86400) / 3600;
-    let m = (seconds % 3600) / 60;
-    if d > 0 {
-        format!("{}d {}h {}m", d, h, m)
-    } else if h > 0 {
-        format!("{}h {}m", h, m)
-    } else {
-        format!("{}m", m)
+    match rc.memory_swap {
+        -1 => "unlimited ⚠".to_string(),
+        s if s <= 0 || s as u64 <= rc.memory_limit => "disabled".to_string(),
+        s => format!("{} swap  ({} total)",
+            units.format_bytes(s as u64 - rc.memory_limit), units.format_bytes(s as u64)),
     }
 }
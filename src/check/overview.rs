@@ -0,0 +1,65 @@
+//! 安全概览：对全部容器的安全配置做汇总计数，供加固评审
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::check::container::{ContainerInfo, SecurityConfig};
+
+/// 加入后可逃逸或显著扩大攻击面的 capability（不含 CAP_ 前缀）
+pub const DANGEROUS_CAPS: &[&str] = &[
+    "ALL", "SYS_ADMIN", "SYS_MODULE", "SYS_PTRACE", "SYS_RAWIO", "SYS_BOOT",
+    "DAC_READ_SEARCH", "NET_ADMIN", "BPF", "PERFMON", "MKNOD",
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecurityOverview {
+    pub total: usize,
+    pub privileged: usize,
+    pub default_caps: usize,         // 未 cap-add / cap-drop
+    pub reduced_caps: usize,         // 有 cap-drop（低于 Docker 默认集合）
+    pub dangerous_caps: usize,       // 加入了 DANGEROUS_CAPS 中任一项
+    pub no_new_privileges: usize,
+    pub read_only_rootfs: usize,
+    pub dangerous_cap_counts: BTreeMap<String, usize>,   // cap → 加入该 cap 的容器数
+}
+
+pub fn build(containers: &[ContainerInfo]) -> SecurityOverview {
+    let mut o = SecurityOverview { total: containers.len(), ..Default::default() };
+    for c in containers {
+        let sec = &c.security;
+        if sec.privileged {
+            o.privileged += 1;
+        } else if sec.capabilities.is_empty() && sec.cap_drop.is_empty() {
+            o.default_caps += 1;
+        }
+        if !sec.cap_drop.is_empty() {
+            o.reduced_caps += 1;
+        }
+        let dangerous = dangerous_caps(sec);
+        if !dangerous.is_empty() {
+            o.dangerous_caps += 1;
+        }
+        for cap in dangerous {
+            *o.dangerous_cap_counts.entry(cap).or_insert(0) += 1;
+        }
+        if sec.no_new_privileges {
+            o.no_new_privileges += 1;
+        }
+        if sec.read_only_rootfs {
+            o.read_only_rootfs += 1;
+        }
+    }
+    o
+}
+
+/// CapAdd 中的危险项，统一为大写、去掉 CAP_ 前缀
+pub fn dangerous_caps(sec: &SecurityConfig) -> Vec<String> {
+    sec.capabilities.iter()
+        .map(|c| normalize_cap(c))
+        .filter(|c| DANGEROUS_CAPS.contains(&c.as_str()))
+        .collect()
+}
+
+fn normalize_cap(cap: &str) -> String {
+    let upper = cap.to_ascii_uppercase();
+    upper.strip_prefix("CAP_").map(String::from).unwrap_or(upper)
+}
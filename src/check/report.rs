@@ -1,10 +1,13 @@
 //! 顶层报告结构体
 
 use serde::{Deserialize, Serialize};
+use crate::check::severity::{Finding, Severity};
 use crate::check::container::ContainerInfo;
-use crate::check::engine::EngineInfo;
+use crate::check::engine::{EngineInfo, DAEMON_LOGS_UNAVAILABLE};
 use crate::check::events::DockerEvent;
 use crate::check::host::HostInfo;
+use crate::check::oom::OomKill;
+use crate::check::overview::SecurityOverview;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckReport {
@@ -13,4 +16,45 @@ pub struct CheckReport {
     pub engine: EngineInfo,
     pub containers: Vec<ContainerInfo>,
     pub events: Vec<DockerEvent>,
+    pub oom_kills: Vec<OomKill>,   // events / 内核日志 / State.OOMKilled 合并
+    pub security_overview: SecurityOverview,
+    pub findings: Vec<Finding>,
+    pub warnings: Vec<String>,   // 部分采集失败（报告仍然输出）
+    #[serde(default)]
+    pub skipped: Vec<SkippedContainer>,   // inspect 失败而未纳入报告的容器
+}
+
+/// 采集时被跳过的容器（权限不足、inspect 期间被删除等）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedContainer {
+    pub id: String,
+    pub reason: String,
+}
+
+impl CheckReport {
+    /// 采集过程中被容忍的失败（--strict 时视为错误）：
+    /// 宿主机部分采集失败、跳过的容器、running 容器缺 stats 或账户表、daemon 日志不可读
+    pub fn collection_problems(&self) -> Vec<String> {
+        let mut problems = self.warnings.clone();
+        for s in &self.skipped {
+            problems.push(format!("skipped container {}: {}", s.id, s.reason));
+        }
+        for c in self.containers.iter().filter(|c| c.status == "running") {
+            if c.resource_usage.is_none() {
+                problems.push(format!("{}: docker stats unavailable", c.name));
+            }
+            if c.users_groups.is_empty() {
+                problems.push(format!("{}: no passwd entries (getent / /etc/passwd)", c.name));
+            }
+        }
+        if self.engine.daemon_logs.iter().any(|l| l == DAEMON_LOGS_UNAVAILABLE) {
+            problems.push(DAEMON_LOGS_UNAVAILABLE.to_string());
+        }
+        problems
+    }
+
+    /// 所有 findings 中的最高严重级别；无 finding 时为 None
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|f| f.severity).max()
+    }
 }
@@ -0,0 +1,65 @@
+//! seccomp profile 摘要：默认动作、放行的 syscall 数、危险 syscall 是否放行
+//!
+//! `--security-opt seccomp=<file>` 时 docker CLI 会把文件内容内联进 SecurityOpt，
+//! 因此值既可能是 JSON 本身，也可能是（旧客户端 / API 直接调用时的）宿主机路径。
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// 放行后可用于逃逸或内核攻击的 syscall
+const DANGEROUS_SYSCALLS: &[&str] = &[
+    "ptrace", "mount", "umount2", "bpf", "kexec_load", "init_module", "finit_module",
+    "unshare", "setns", "open_by_handle_at", "keyctl", "perf_event_open",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeccompSummary {
+    pub source: String,               // inline 或 profile 文件路径
+    pub default_action: String,       // SCMP_ACT_ERRNO / SCMP_ACT_ALLOW / ...
+    pub allowed_syscalls: Option<usize>,   // ALLOW 规则列出的 syscall 数；默认放行时为 None
+    pub dangerous_allowed: Vec<String>,
+}
+
+/// 解析 SecurityOpt 中 seccomp= 的值；unconfined / 读不到的路径 / 非法 JSON 返回 None
+pub fn summarize(value: &str) -> Option<SeccompSummary> {
+    let (source, content) = if value.trim_start().starts_with('{') {
+        ("inline".to_string(), value.to_string())
+    } else if value.starts_with('/') {
+        (value.to_string(), std::fs::read_to_string(value).ok()?)
+    } else {
+        return None;
+    };
+    let profile: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    let default_action = profile["defaultAction"].as_str().unwrap_or("").to_string();
+    let default_allows = is_allow(&default_action);
+
+    let mut allowed = BTreeSet::new();
+    let mut denied = BTreeSet::new();
+    for rule in profile["syscalls"].as_array().into_iter().flatten() {
+        let action = rule["action"].as_str().unwrap_or("");
+        // 旧格式单个 "name"，新格式 "names" 数组
+        let names = rule["names"].as_array().into_iter().flatten()
+            .chain(std::iter::once(&rule["name"]))
+            .filter_map(|n| n.as_str());
+        for n in names {
+            if is_allow(action) { allowed.insert(n.to_string()); } else { denied.insert(n.to_string()); }
+        }
+    }
+
+    let dangerous_allowed = DANGEROUS_SYSCALLS.iter()
+        .filter(|s| if default_allows { !denied.contains(**s) } else { allowed.contains(**s) })
+        .map(|s| s.to_string())
+        .collect();
+
+    Some(SeccompSummary {
+        source,
+        default_action,
+        allowed_syscalls: (!default_allows).then_some(allowed.len()),
+        dangerous_allowed,
+    })
+}
+
+fn is_allow(action: &str) -> bool {
+    matches!(action, "SCMP_ACT_ALLOW" | "SCMP_ACT_LOG")
+}
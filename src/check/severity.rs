@@ -0,0 +1,64 @@
+//! 统一的 finding 模型：严重级别 + 稳定 ID，供 --fail-on 和机器消费
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// 严重级别，按声明顺序可比较（Info 最低）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub const ALL: &'static [Severity] = &[
+        Severity::Info,
+        Severity::Low,
+        Severity::Medium,
+        Severity::High,
+        Severity::Critical,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info     => "info",
+            Severity::Low      => "low",
+            Severity::Medium   => "medium",
+            Severity::High     => "high",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        Self::ALL.iter()
+            .copied()
+            .find(|v| v.as_str() == lower)
+            .ok_or_else(|| format!("unknown severity '{}' (valid: info, low, medium, high, critical)", s))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    pub id: String,                  // 稳定规则 ID，如 container.crash_loop
+    pub severity: Severity,
+    pub container: Option<String>,   // 容器名；None 表示宿主机/引擎层
+    pub owner: Option<String>,       // 由容器标签得出的负责人；None 显示为 unassigned
+    pub title: String,
+    pub detail: String,
+}
@@ -0,0 +1,112 @@
+//! 容器时间线：生命周期时间戳、健康检查与 docker events 合并为按时间排序的条目
+//! 同时推导最近一次退出（last_death）
+
+use crate::check::container::{ContainerInfo, DeathInfo, TimelineEntry};
+use crate::check::events::DockerEvent;
+use chrono::{DateTime, Utc};
+
+/// oom 事件与随后 die 事件的最大间隔
+const OOM_DIE_WINDOW_SECS: i64 = 5;
+
+pub fn attach(containers: &mut [ContainerInfo], events: &[DockerEvent]) {
+    for c in containers {
+        c.timeline = build(c, events);
+        c.last_death = last_death(c, events);
+    }
+}
+
+pub fn build(c: &ContainerInfo, events: &[DockerEvent]) -> Vec<TimelineEntry> {
+    let mut out = Vec::new();
+    let mut push = |time: Option<DateTime<Utc>>, source: &str, label: String| {
+        if let Some(time) = time {
+            out.push(TimelineEntry { time, source: source.to_string(), label });
+        }
+    };
+
+    let lc = &c.lifecycle;
+    push(lc.created, "state", "created".to_string());
+    push(lc.started, "state", "started".to_string());
+    if c.status != "running" {
+        push(lc.finished, "state", format!("finished (exit {}{})",
+            c.exit_code, if c.oom_killed { ", OOM-killed" } else { "" }));
+    }
+    for h in &lc.health_checks {
+        let result = if h.exit_code == 0 { "ok".to_string() } else { format!("failed (exit {})", h.exit_code) };
+        push(h.start, "health", format!("health check {}", result));
+    }
+
+    // 事件的 actor_id 与 c.id 同样按 --id-length 截断
+    for ev in events.iter().filter(|e| e.event_type == "container" && c.id.starts_with(&e.actor_id)) {
+        push(ev.time, "event", ev.action.clone());
+    }
+
+    out.sort_by_key(|e| e.time);
+    out
+}
+
+/// 取 State（仅非 running 时可信）与最近 die 事件中较新的一次退出；
+/// die 前几秒内有 oom 事件即视为 OOM
+pub fn last_death(c: &ContainerInfo, events: &[DockerEvent]) -> Option<DeathInfo> {
+    let own: Vec<&DockerEvent> = events.iter()
+        .filter(|e| e.event_type == "container" && c.id.starts_with(&e.actor_id))
+        .collect();
+
+    let from_state = c.lifecycle.finished
+        .filter(|_| c.status != "running")
+        .map(|t| (t, c.exit_code, c.oom_killed));
+    let from_event = own.iter()
+        .filter(|e| e.action == "die")
+        .filter_map(|e| {
+            let code = e.attributes.get("exitCode").and_then(|v| v.parse().ok()).unwrap_or(0);
+            e.time.map(|t| (t, code, false))
+        })
+        .max_by_key(|(t, _, _)| *t);
+
+    let (finished_at, exit_code, oom) = match (from_state, from_event) {
+        (Some(s), Some(e)) if e.0 > s.0 => e,
+        (Some(s), _) => s,
+        (None, e) => e?,
+    };
+    let oom_event = own.iter().any(|e| e.action == "oom" && e.time.is_some_and(|t| {
+        let gap = (finished_at - t).num_seconds();
+        (0..=OOM_DIE_WINDOW_SECS).contains(&gap)
+    }));
+
+    Some(DeathInfo {
+        finished_at,
+        exit_code,
+        oom_killed: oom || oom_event,
+        signal: signal_name(exit_code).map(String::from),
+    })
+}
+
+/// 128+N 退出码对应的信号名
+fn signal_name(exit_code: i64) -> Option<&'static str> {
+    match exit_code - 128 {
+        1  => Some("SIGHUP"),
+        2  => Some("SIGINT"),
+        3  => Some("SIGQUIT"),
+        6  => Some("SIGABRT"),
+        7  => Some("SIGBUS"),
+        9  => Some("SIGKILL"),
+        11 => Some("SIGSEGV"),
+        13 => Some("SIGPIPE"),
+        15 => Some("SIGTERM"),
+        _  => None,
+    }
+}
+
+/// 退出原因的人类可读标签："OOM" / "SIGSEGV" / "command not found" 等
+pub fn death_label(d: &DeathInfo) -> Option<String> {
+    if d.oom_killed {
+        return Some("OOM".to_string());
+    }
+    if let Some(sig) = &d.signal {
+        return Some(sig.clone());
+    }
+    match d.exit_code {
+        126 => Some("not executable".to_string()),
+        127 => Some("command not found".to_string()),
+        _   => None,
+    }
+}
@@ -0,0 +1,65 @@
+//! --top：类似 docker stats 的实时资源视图，叠加 sedock 的安全标记
+
+use crate::check::collector;
+use crate::check::container::ResourceUsage;
+use crate::check::output;
+use crate::cli::CheckArgs;
+use crate::utils::{Result, SedockerError};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct TopRow {
+    pub id: String,
+    pub name: String,
+    pub privileged: bool,
+    pub usage: ResourceUsage,
+}
+
+pub fn run(args: &CheckArgs) -> Result<()> {
+    let running = Arc::new(AtomicBool::new(true));
+    let r = running.clone();
+    ctrlc::set_handler(move || {
+        r.store(false, Ordering::SeqCst);
+    }).map_err(|e| SedockerError::System(format!("Error setting Ctrl-C handler: {}", e)))?;
+
+    // 名称 / privileged 只在首次见到容器时 inspect 一次
+    let mut meta: HashMap<String, (String, bool)> = HashMap::new();
+    let interval = args.top_interval.max(1);
+
+    while running.load(Ordering::SeqCst) {
+        let stats = collector::fetch_stats_all();
+        let mut rows: Vec<TopRow> = stats.into_iter().map(|(id, usage)| {
+            let (name, privileged) = meta.entry(id.clone())
+                .or_insert_with(|| inspect_meta(&id))
+                .clone();
+            TopRow { id, name, privileged, usage }
+        }).collect();
+
+        match args.stats_sort.as_str() {
+            "mem" => rows.sort_by_key(|r| std::cmp::Reverse(r.usage.memory_usage)),
+            _     => rows.sort_by(|a, b| b.usage.cpu_percent.total_cmp(&a.usage.cpu_percent)),
+        }
+        output::display_top(&rows, &args.stats_sort, interval, args.units);
+
+        // 分段睡眠，Ctrl+C 后尽快退出
+        for _ in 0..interval * 10 {
+            if !running.load(Ordering::SeqCst) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+    Ok(())
+}
+
+fn inspect_meta(id: &str) -> (String, bool) {
+    match collector::docker_inspect(id) {
+        Ok(j) => (
+            j["Name"].as_str().unwrap_or(id).trim_start_matches('/').to_string(),
+            j["HostConfig"]["Privileged"].as_bool().unwrap_or(false),
+        ),
+        Err(_) => (id.to_string(), false),
+    }
+}
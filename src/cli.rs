@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use crate::check::severity::Severity;
+use crate::utils::{OutputFormat, Units};
+use crate::utils::format::parse_monitor_format;
 
 #[derive(Parser)]
 #[command(name = "sedock")]
@@ -7,38 +10,401 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Characters of container IDs to show and match on (12 = docker short ID, up to 64 = full)
+    #[arg(long, global = true, default_value_t = 12, value_name = "N",
+          value_parser = clap::value_parser!(u8).range(12..=64))]
+    pub id_length: u8,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Monitor file access in a directory
     #[command(arg_required_else_help = true)]
-    Monitor {
-        /// Directory to monitor
-        #[arg(short, long)]
-        directory: String,
-        
-        /// Output format (text or json)
-        #[arg(short, long, default_value = "text")]
-        format: String,
-        
-        /// Disable event deduplication (show all events)
-        #[arg(short, long)]
-        verbose: bool,
-    },
+    Monitor(MonitorArgs),
     
     /// Check and collect Docker container information
-    Check {
-        /// Specific container ID or name
-        #[arg(short, long)]
-        container: Option<String>,
-        
-        /// Output format (text or json)
-        #[arg(short, long, default_value = "text")]
-        output: String,
-        
-        /// Show detailed information
-        #[arg(short, long, default_value = "false")]
-        verbose: bool,
-    },
-}
\ No newline at end of file
+    Check(CheckArgs),
+
+    /// Monitor a directory and periodically report which containers touched which files
+    #[command(arg_required_else_help = true)]
+    Trace(TraceArgs),
+
+    /// Query past Docker events filtered by type and action (timestamps in local time)
+    Events(EventsArgs),
+
+    /// Preflight: verify root, fanotify, Docker and optional tools
+    Doctor,
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Specific container ID or name
+    #[arg(short, long)]
+    pub container: Option<String>,
+
+    /// Check only the container that owns this host PID (e.g. one reported by monitor)
+    #[arg(long, conflicts_with = "container")]
+    pub pid: Option<i32>,
+    
+    /// Only containers whose image (Config.Image) contains this string
+    #[arg(long)]
+    pub image: Option<String>,
+
+    /// Group containers under per-image headers with counts and aggregate usage
+    #[arg(long)]
+    pub group_by_image: bool,
+
+    /// Skip a container by ID prefix or exact name (repeatable)
+    #[arg(long = "exclude-container", value_name = "ID|NAME")]
+    pub exclude_containers: Vec<String>,
+
+    /// Only check containers of this compose project (com.docker.compose.project label), grouped with a rollup
+    #[arg(long, value_name = "NAME")]
+    pub project: Option<String>,
+    
+    /// Output format (text, json, jsonl or influx)
+    #[arg(short, long, default_value = "text")]
+    pub output: OutputFormat,
+
+    /// Write <name>.json per container plus host.json, engine.json and an index.json manifest
+    /// into this directory (created if missing) instead of printing the report
+    #[arg(long, value_name = "PATH")]
+    pub output_dir: Option<String>,
+
+    /// Emit JSON on a single line instead of pretty-printed (implied by jsonl)
+    #[arg(long)]
+    pub compact: bool,
+    
+    /// More detail, repeatable: -v full logs, events and daemon logs; -vv also per-file mount
+    /// permissions; -vvv also the raw docker inspect JSON per container
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Size units for text output: binary (KiB/MiB/GiB, as docker) or si (kB/MB/GB)
+    #[arg(long, default_value = "binary", value_name = "UNITS")]
+    pub units: Units,
+
+    /// Show all container labels, including com.docker.* / org.opencontainers.*
+    #[arg(long)]
+    pub all_labels: bool,
+
+    /// Containers collected in parallel (default: CPU count, at most 8); 1 = serial, for debugging
+    #[arg(short, long, value_name = "N")]
+    pub jobs: Option<usize>,
+
+    /// Collect writable-layer changes (docker diff) per container
+    #[arg(long)]
+    pub diff: bool,
+
+    /// Attach the full `docker inspect` JSON to each container as "raw" (implied by -vvv)
+    #[arg(long, visible_alias = "raw")]
+    pub raw_inspect: bool,
+
+    /// Regex to scan container logs for (repeatable; replaces the built-in failure patterns)
+    #[arg(long = "log-pattern", value_name = "REGEX")]
+    pub log_patterns: Vec<String>,
+
+    /// Strip ANSI escapes and control characters from log lines in JSON output too
+    /// (always done for text output)
+    #[arg(long)]
+    pub sanitize_logs: bool,
+
+    /// With --verbose, keep at most this many bytes from the end of each container's log
+    #[arg(long, value_name = "BYTES", default_value_t = 1024 * 1024)]
+    pub log_max_bytes: usize,
+
+    /// Poll `docker version` for up to this many seconds before collecting (for boot-time units)
+    #[arg(long, value_name = "SECS")]
+    pub wait_for_docker: Option<u64>,
+
+    /// Live resource view instead of the report: refreshing table of all running containers
+    #[arg(long)]
+    pub top: bool,
+
+    /// Sort key for --top: cpu or mem
+    #[arg(long, default_value = "cpu", value_parser = ["cpu", "mem"])]
+    pub stats_sort: String,
+
+    /// Refresh interval for --top
+    #[arg(long, default_value_t = 2, value_name = "SECS")]
+    pub top_interval: u64,
+
+    /// Take two stats samples this many seconds apart and report IO rates and interval CPU%
+    #[arg(long, value_name = "SECS")]
+    pub sample: Option<u64>,
+
+    /// Replace hostname (whole words only), IPv4/IPv6/MAC addresses and mount source paths
+    /// with stable placeholders (mapping is printed to stderr)
+    #[arg(long)]
+    pub anonymize: bool,
+
+    /// Text output: show only containers with findings, each followed by its findings
+    #[arg(long)]
+    pub only_warnings: bool,
+
+    /// Print containers as an aligned table instead of the full report (text output)
+    #[arg(long)]
+    pub table: bool,
+
+    /// Flag a crash loop when restart count exceeds this value...
+    #[arg(long, default_value_t = 5, value_name = "N")]
+    pub crash_loop_restarts: i64,
+
+    /// ...and the container has been up for less than this many seconds
+    #[arg(long, default_value_t = 60, value_name = "SECS")]
+    pub crash_loop_uptime: i64,
+
+    /// Flag containers whose memory usage reaches this percent of their memory limit
+    #[arg(long, default_value_t = 90.0, value_name = "PCT")]
+    pub mem_warn_percent: f64,
+
+    /// Flag containers whose process count reaches this percent of their pids limit
+    #[arg(long, default_value_t = 90.0, value_name = "PCT")]
+    pub pids_warn_percent: f64,
+
+    /// Flag containers whose CPU usage reaches this percent of their CPU quota
+    #[arg(long, default_value_t = 95.0, value_name = "PCT")]
+    pub cpu_warn_percent: f64,
+
+    /// Flag running containers created more than this many days ago (0 disables)
+    #[arg(long, default_value_t = 180, value_name = "DAYS")]
+    pub max_age_days: u64,
+
+    /// Container label naming the owner of a finding (falls back to "owner", then the compose project)
+    #[arg(long, default_value = "maintainer", value_name = "KEY")]
+    pub owner_label: String,
+
+    /// Exit non-zero if collection was incomplete (host warnings, skipped containers, missing
+    /// stats or accounts, unreadable daemon logs); the report is still printed
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Exit non-zero if any finding is at or above this severity (info, low, medium, high, critical)
+    #[arg(long, value_name = "SEVERITY")]
+    pub fail_on: Option<Severity>,
+
+    /// After the report, stream Docker events live until Ctrl+C
+    #[arg(long)]
+    pub follow_events: bool,
+}
+
+#[derive(Args)]
+pub struct MonitorArgs {
+    /// Directory to monitor (events on its direct children), or a single regular file
+    #[arg(short, long)]
+    pub directory: String,
+    
+    /// Output format (text, json or jsonl; json is already one event per line)
+    #[arg(short, long, default_value = "text", value_parser = parse_monitor_format)]
+    pub format: OutputFormat,
+    
+    /// Custom line format, e.g. "{timestamp} {pid} {process_path:short} {file_path}"
+    /// (overrides --format; modifiers: short, upper, lower)
+    #[arg(long)]
+    pub template: Option<String>,
+    
+    /// Disable event deduplication (show all events)
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Resolve uid/gid to user/group names (host /etc, or the container's /etc for container processes)
+    #[arg(long)]
+    pub resolve_names: bool,
+
+    /// Event time base: wall, monotonic (matches dmesg) or boot (CLOCK_BOOTTIME, matches /proc/uptime);
+    /// non-wall clocks add clock_secs to JSON and prefix text lines
+    #[arg(long, default_value = "wall", value_parser = ["wall", "monotonic", "boot"])]
+    pub clock: String,
+
+    /// Run --exec when an event's path matches this glob (repeatable; without '/' matches the file name)
+    #[arg(long = "alert-path", value_name = "GLOB")]
+    pub alert_paths: Vec<String>,
+
+    /// Shell command run for matching events; fields in SEDOCK_FILE, SEDOCK_PID, SEDOCK_EVENT, SEDOCK_CONTAINER
+    #[arg(long, value_name = "CMD")]
+    pub exec: Option<String>,
+
+    /// Maximum concurrently running --exec hooks; further matches are skipped
+    #[arg(long, default_value_t = 4, value_name = "N")]
+    pub max_hooks: usize,
+
+    /// Instead of per-event output, print the N most-accessed files every --interval seconds
+    #[arg(long, value_name = "N")]
+    pub top_files: Option<usize>,
+
+    /// Aggregation window in seconds for --top-files
+    #[arg(long, default_value_t = 5, value_name = "SECS")]
+    pub interval: u64,
+
+    /// Every N seconds, re-scan bin directories whose mtime changed (default: scan once at startup)
+    #[arg(long, value_name = "SECS")]
+    pub bincache_refresh: Option<u64>,
+
+    /// Serve Prometheus counters at http://<ADDR>/metrics while monitoring (e.g. 127.0.0.1:9464)
+    #[arg(long, value_name = "IP:PORT")]
+    pub metrics_addr: Option<String>,
+
+    /// Print an event summary (counts, top processes and files) on exit and on SIGUSR1
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Spend this many milliseconds filling the process cache before output; events in that
+    /// window are dropped
+    #[arg(long, value_name = "MS")]
+    pub warmup: Option<u64>,
+
+    /// Above this many events per second (or when the output queue drops events), emit only
+    /// pid + path and skip exe/uid/container resolution until the rate falls back
+    #[arg(long, value_name = "EVENTS/S")]
+    pub max_rate: Option<u64>,
+
+    /// Read buffer size in bytes for fanotify events
+    #[arg(long, default_value_t = 16384, value_name = "BYTES")]
+    pub buffer_size: usize,
+
+    /// Lift the kernel event queue and mark limits (FAN_UNLIMITED_QUEUE/MARKS) for high-volume captures
+    #[arg(long)]
+    pub unlimited_queue: bool,
+
+    /// Events to mark, comma-separated: open,access,modify,close,close_write,close_nowrite,open_exec (alias exec)
+    #[arg(long, default_value = "open,access,modify")]
+    pub mask: String,
+
+    /// Only show events from containers whose ID starts with this prefix (repeatable)
+    #[arg(long = "include-container", value_name = "ID")]
+    pub include_containers: Vec<String>,
+
+    /// Hide events from containers whose ID starts with this prefix (repeatable, wins over include)
+    #[arg(long = "exclude-container", value_name = "ID")]
+    pub exclude_containers: Vec<String>,
+
+    /// Show host (non-container) events when an include filter is active;
+    /// on its own, restricts output to host events
+    #[arg(long)]
+    pub include_host: bool,
+}
+
+#[derive(Args)]
+pub struct EventsArgs {
+    /// How far back to look: a duration (2h, 30m) or timestamp, as accepted by docker events
+    #[arg(long, default_value = "24h")]
+    pub since: String,
+
+    /// Only events of this type (container, image, network, volume, daemon, ...)
+    #[arg(long = "type", value_name = "TYPE")]
+    pub event_type: Option<String>,
+
+    /// Only these actions, comma-separated (e.g. die,oom); "health_status" matches all health results
+    #[arg(long = "action", value_name = "ACTION", value_delimiter = ',')]
+    pub actions: Vec<String>,
+
+    /// Stream new events live (same filters, --since ignored) until Ctrl+C
+    #[arg(long)]
+    pub follow: bool,
+
+    /// Output format (text, json or jsonl; json is one event per line)
+    #[arg(short, long, default_value = "text", value_parser = parse_monitor_format)]
+    pub format: OutputFormat,
+}
+
+// trace 只接受聚合模式下实际生效的 monitor 参数
+#[derive(Args)]
+pub struct TraceArgs {
+    /// Directory to monitor (events on its direct children), or a single regular file
+    #[arg(short, long)]
+    pub directory: String,
+
+    /// Report window in seconds
+    #[arg(long, default_value_t = 5, value_name = "SECS")]
+    pub interval: u64,
+
+    /// Events to mark, comma-separated: open,access,modify,close,close_write,close_nowrite,open_exec (alias exec)
+    #[arg(long, default_value = "open,access,modify")]
+    pub mask: String,
+
+    /// Only count events from containers whose ID starts with this prefix (repeatable)
+    #[arg(long = "include-container", value_name = "ID")]
+    pub include_containers: Vec<String>,
+
+    /// Ignore events from containers whose ID starts with this prefix (repeatable, wins over include)
+    #[arg(long = "exclude-container", value_name = "ID")]
+    pub exclude_containers: Vec<String>,
+
+    /// Count host (non-container) events when an include filter is active;
+    /// on its own, restricts the report to host events
+    #[arg(long)]
+    pub include_host: bool,
+
+    /// Run --exec when an event's path matches this glob (repeatable; without '/' matches the file name)
+    #[arg(long = "alert-path", value_name = "GLOB")]
+    pub alert_paths: Vec<String>,
+
+    /// Shell command run for matching events; fields in SEDOCK_FILE, SEDOCK_PID, SEDOCK_EVENT, SEDOCK_CONTAINER
+    #[arg(long, value_name = "CMD")]
+    pub exec: Option<String>,
+
+    /// Maximum concurrently running --exec hooks; further matches are skipped
+    #[arg(long, default_value_t = 4, value_name = "N")]
+    pub max_hooks: usize,
+
+    /// Every N seconds, re-scan bin directories whose mtime changed (default: scan once at startup)
+    #[arg(long, value_name = "SECS")]
+    pub bincache_refresh: Option<u64>,
+
+    /// Serve Prometheus counters at http://<ADDR>/metrics while tracing (e.g. 127.0.0.1:9464)
+    #[arg(long, value_name = "IP:PORT")]
+    pub metrics_addr: Option<String>,
+
+    /// Print an event summary (counts, top processes and files) on exit and on SIGUSR1
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Spend this many milliseconds filling the process cache before counting; events in that
+    /// window are dropped
+    #[arg(long, value_name = "MS")]
+    pub warmup: Option<u64>,
+
+    /// Above this many events per second (or when the output queue drops events), skip exe
+    /// resolution until the rate falls back
+    #[arg(long, value_name = "EVENTS/S")]
+    pub max_rate: Option<u64>,
+
+    /// Read buffer size in bytes for fanotify events
+    #[arg(long, default_value_t = 16384, value_name = "BYTES")]
+    pub buffer_size: usize,
+
+    /// Lift the kernel event queue and mark limits (FAN_UNLIMITED_QUEUE/MARKS) for high-volume captures
+    #[arg(long)]
+    pub unlimited_queue: bool,
+}
+
+impl TraceArgs {
+    /// 转为 monitor 参数；逐条输出相关的选项取默认值
+    pub fn monitor_args(&self) -> MonitorArgs {
+        MonitorArgs {
+            directory: self.directory.clone(),
+            format: OutputFormat::Text,
+            template: None,
+            verbose: false,
+            resolve_names: false,
+            clock: "wall".to_string(),
+            alert_paths: self.alert_paths.clone(),
+            exec: self.exec.clone(),
+            max_hooks: self.max_hooks,
+            top_files: None,
+            interval: self.interval,
+            bincache_refresh: self.bincache_refresh,
+            metrics_addr: self.metrics_addr.clone(),
+            summary: self.summary,
+            warmup: self.warmup,
+            max_rate: self.max_rate,
+            buffer_size: self.buffer_size,
+            unlimited_queue: self.unlimited_queue,
+            mask: self.mask.clone(),
+            include_containers: self.include_containers.clone(),
+            exclude_containers: self.exclude_containers.clone(),
+            include_host: self.include_host,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! 预检（sedock doctor）：逐项检查运行环境，缺少硬性依赖时返回错误
+
+use crate::check::{engine, host};
+use crate::monitor::fanotify;
+use crate::utils::{caps, Result, SedockerError};
+
+/// 可选命令及缺失时受影响的功能
+const OPTIONAL_COMMANDS: &[(&str, &str)] = &[
+    ("getent",      "container user/group names (falls back to /etc files)"),
+    ("nvidia-smi",  "GPU information"),
+    ("journalctl",  "daemon logs (falls back to /var/log/docker.log)"),
+    ("timedatectl", "NTP sync status"),
+];
+
+pub fn run_doctor() -> Result<()> {
+    let mut missing = 0;
+    let mut report = |ok: bool, hard: bool, name: &str, detail: String| {
+        let mark = match (ok, hard) {
+            (true, _)      => "✓",
+            (false, true)  => { missing += 1; "✗" }
+            (false, false) => "⚠",
+        };
+        println!("  {} {:<14} {}", mark, name, detail);
+    };
+
+    println!("Requirements:");
+    let euid = unsafe { libc::geteuid() };
+    report(euid == 0, true, "root", format!("euid {}", euid));
+
+    // root 但 capability 受限（容器、systemd CapabilityBoundingSet 等）时 fanotify 仍会失败
+    match caps::OwnCaps::read() {
+        Some(own) => {
+            let sys_admin = own.has_effective(caps::CAP_SYS_ADMIN);
+            let detail = if sys_admin {
+                "effective".to_string()
+            } else if own.bounding & (1 << caps::CAP_SYS_ADMIN) == 0 {
+                "not in bounding set — cannot be gained even as root; fanotify will fail".to_string()
+            } else {
+                "not effective — fanotify will fail".to_string()
+            };
+            report(sys_admin, true, "CAP_SYS_ADMIN", detail);
+            let full = caps::kernel_full_mask().unwrap_or(own.bounding);
+            report(true, false, "CapEff", describe_caps(own.effective, full));
+            report(true, false, "CapBnd", describe_caps(own.bounding, full));
+        }
+        None => report(false, false, "capabilities", "cannot read /proc/self/status".to_string()),
+    }
+
+    match fanotify::probe() {
+        Ok(())  => report(true, true, "fanotify", "supported".to_string()),
+        Err(e)  => report(false, true, "fanotify", e.to_string()),
+    }
+
+    match engine::collect_version() {
+        Ok(v)  => report(true, true, "docker", format!("server {} (API {})", v.server_version, v.api_version)),
+        Err(e) => report(false, true, "docker", e.to_string().trim().to_string()),
+    }
+
+    let cgroup = host::detect_cgroup_version();
+    report(cgroup != "unknown", false, "cgroup", cgroup);
+
+    println!("\nOptional commands:");
+    for (cmd, purpose) in OPTIONAL_COMMANDS {
+        match find_in_path(cmd) {
+            Some(path) => report(true, false, cmd, path),
+            None       => report(false, false, cmd, format!("not found — {}", purpose)),
+        }
+    }
+
+    if missing > 0 {
+        return Err(SedockerError::System(format!("{} hard requirement(s) missing", missing)));
+    }
+    println!("\nAll requirements met.");
+    Ok(())
+}
+
+/// 完整集合显示 all，缺少少数几项时列出缺失项，否则列出已有项
+fn describe_caps(mask: u64, full: u64) -> String {
+    let have = caps::cap_names(mask & full);
+    let lacking = caps::cap_names(full & !mask);
+    if lacking.is_empty() {
+        format!("all ({})", have.len())
+    } else if have.is_empty() {
+        "none".to_string()
+    } else if lacking.len() <= have.len() {
+        format!("all except {}", lacking.join(", "))
+    } else {
+        have.join(", ")
+    }
+}
+
+fn find_in_path(cmd: &str) -> Option<String> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(cmd))
+        .find(|p| p.is_file())
+        .map(|p| p.display().to_string())
+}
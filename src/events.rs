@@ -0,0 +1,39 @@
+//! events：按类型 / 动作过滤的历史 Docker 事件查询；--follow 时实时跟随
+
+use crate::check::events::{self, DockerEvent};
+use crate::check::output;
+use crate::cli::EventsArgs;
+use crate::utils::Result;
+
+pub fn run_events(args: &EventsArgs) -> Result<()> {
+    let wanted = |ev: &DockerEvent| {
+        args.event_type.as_deref().is_none_or(|t| ev.event_type == t)
+            && (args.actions.is_empty()
+                || args.actions.iter().any(|a| events::action_matches(&ev.action, a)))
+    };
+
+    // --follow：只输出之后发生的事件，直到 Ctrl+C
+    if args.follow {
+        if !args.format.is_json() {
+            eprintln!("Following Docker events (Ctrl+C to stop)...");
+        }
+        return events::follow(|ev| {
+            if wanted(ev) {
+                output::display_event(ev, args.format);
+            }
+        });
+    }
+
+    let matched: Vec<_> = events::fetch(&args.since)?
+        .into_iter()
+        .filter(|ev| wanted(ev))
+        .collect();
+
+    if !args.format.is_json() {
+        eprintln!("{} event(s) since {}", matched.len(), args.since);
+    }
+    for ev in &matched {
+        output::display_event(ev, args.format);
+    }
+    Ok(())
+}
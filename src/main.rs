@@ -1,6 +1,9 @@
 mod cli;
 mod monitor;
 mod check;
+mod doctor;
+mod events;
+mod trace;
 mod utils;
 
 use clap::Parser;
@@ -8,13 +11,23 @@ use cli::{Cli, Commands};
 
 fn main() {
     let cli = Cli::parse();
+    utils::id::set_id_length(cli.id_length as usize);
     
     let result = match cli.command {
-        Commands::Monitor { directory, format, verbose } => {
-            monitor::run_monitor(&directory, &format, verbose)
+        Commands::Monitor(args) => {
+            monitor::run_monitor(&args)
         }
-        Commands::Check { container, output, verbose } => {
-            check::run_check(container, &output, verbose)
+        Commands::Check(args) => {
+            check::run_check(&args)
+        }
+        Commands::Trace(args) => {
+            trace::run_trace(&args)
+        }
+        Commands::Events(args) => {
+            events::run_events(&args)
+        }
+        Commands::Doctor => {
+            doctor::run_doctor()
         }
     };
     
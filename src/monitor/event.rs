@@ -1,3 +1,4 @@
+use crate::monitor::fanotify;
 use crate::utils::{EventType, FileAccessEvent};
 use chrono::Local;
 
@@ -27,25 +28,46 @@ impl EventDeduplicator {
     }
 }
 
-pub fn create_event(
-    event_type: EventType,
-    pid: i32,
-    container_pid: Option<i32>,
-    uid: u32,
-    gid: u32,
-    process_path: String,
-    file_path: String,
-    container_id: Option<String>,
-) -> FileAccessEvent {
+/// 事件时间基准（--clock）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Clock {
+    Wall,
+    Monotonic,   // CLOCK_MONOTONIC：与 dmesg 时间戳一致
+    Boot,        // CLOCK_BOOTTIME：含挂起时间，与 /proc/uptime 一致
+}
+
+impl Clock {
+    pub fn from_arg(s: &str) -> Self {
+        match s {
+            "monotonic" => Clock::Monotonic,
+            "boot"      => Clock::Boot,
+            _           => Clock::Wall,
+        }
+    }
+
+    /// 非 wall 时钟下的秒数（开机起算）
+    pub fn now_secs(self) -> Option<f64> {
+        let id = match self {
+            Clock::Wall      => return None,
+            Clock::Monotonic => libc::CLOCK_MONOTONIC,
+            Clock::Boot      => libc::CLOCK_BOOTTIME,
+        };
+        let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        if unsafe { libc::clock_gettime(id, &mut ts) } != 0 {
+            return None;
+        }
+        Some(ts.tv_sec as f64 + ts.tv_nsec as f64 / 1e9)
+    }
+}
+
+/// 事件的公共部分（类型、时间戳、mask 与标志名），其余字段由调用方以
+/// `FileAccessEvent { pid, .., ..event::stamped(event_type, mask) }` 填写
+pub fn stamped(event_type: EventType, mask: u64) -> FileAccessEvent {
     FileAccessEvent {
         event_type: event_type.to_string(),
         timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-        pid,
-        container_pid,
-        uid,
-        gid,
-        process_path,
-        file_path,
-        container_id,
+        mask,
+        flags: fanotify::mask_flags(mask),
+        ..Default::default()
     }
 }
\ No newline at end of file
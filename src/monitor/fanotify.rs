@@ -1,18 +1,104 @@
-use crate::monitor::{event, process};
-use crate::utils::{EventType, Result, SedockerError};
+use crate::cli::MonitorArgs;
+use crate::monitor::{event, filter, hook, metrics, names, process, sampling, summary, Aggregator, EventFormat};
+use crate::utils::{caps, EventType, FileAccessEvent, Result, SedockerError};
 use lru::LruCache;
 use std::num::NonZeroUsize;
 use std::os::unix::io::RawFd;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{SyncSender, TrySendError};
 use std::sync::Arc;
 
 const FAN_CLASS_NOTIF: u32 = 0x00000000;
+const FAN_UNLIMITED_QUEUE: u32 = 0x00000010;
+const FAN_UNLIMITED_MARKS: u32 = 0x00000020;
 const FAN_MARK_ADD: u32 = 0x00000001;
 const FAN_OPEN: u64 = 0x00000020;
 const FAN_ACCESS: u64 = 0x00000001;
 const FAN_MODIFY: u64 = 0x00000002;
+const FAN_CLOSE_WRITE: u64 = 0x00000008;
+const FAN_CLOSE_NOWRITE: u64 = 0x00000010;
+const FAN_OPEN_EXEC: u64 = 0x00001000;
+const FAN_Q_OVERFLOW: u64 = 0x00004000;
 const FAN_EVENT_ON_CHILD: u64 = 0x08000000;
 
+/// 事件循环 poll 超时：空闲时的唤醒间隔，决定 Ctrl+C / SIGUSR1 的响应延迟
+const POLL_TIMEOUT_MS: libc::c_int = 100;
+
+/// 读线程与处理线程之间的队列容量（事件数）；满时新事件被丢弃并计数
+const EVENT_QUEUE_CAPACITY: usize = 8192;
+
+/// --mask 可用的符号名 → mask 位
+const MASK_TOKENS: &[(&str, u64)] = &[
+    ("open",          FAN_OPEN),
+    ("access",        FAN_ACCESS),
+    ("modify",        FAN_MODIFY),
+    ("close",         FAN_CLOSE_WRITE | FAN_CLOSE_NOWRITE),
+    ("close_write",   FAN_CLOSE_WRITE),
+    ("close_nowrite", FAN_CLOSE_NOWRITE),
+    ("open_exec",     FAN_OPEN_EXEC),
+    ("exec",          FAN_OPEN_EXEC),
+];
+
+/// 解析 "open,modify,open_exec" 形式的 mask 描述，未知名称报错
+pub fn parse_mask(spec: &str) -> Result<u64> {
+    let mut mask = 0u64;
+    for token in spec.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        let bits = MASK_TOKENS.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(token))
+            .map(|(_, bits)| *bits)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = MASK_TOKENS.iter().map(|(n, _)| *n).collect();
+                SedockerError::Parse(format!(
+                    "unknown mask event '{}' (valid: {})", token, valid.join(", ")))
+            })?;
+        mask |= bits;
+    }
+    if mask == 0 {
+        return Err(SedockerError::Parse("mask must contain at least one event".to_string()));
+    }
+    Ok(mask)
+}
+
+/// fanotify mask 位 → 名称，用于 JSON 输出中解码原始 mask
+const MASK_NAMES: &[(u64, &str)] = &[
+    (0x00000001, "FAN_ACCESS"),
+    (0x00000002, "FAN_MODIFY"),
+    (0x00000004, "FAN_ATTRIB"),
+    (0x00000008, "FAN_CLOSE_WRITE"),
+    (0x00000010, "FAN_CLOSE_NOWRITE"),
+    (0x00000020, "FAN_OPEN"),
+    (0x00000040, "FAN_MOVED_FROM"),
+    (0x00000080, "FAN_MOVED_TO"),
+    (0x00000100, "FAN_CREATE"),
+    (0x00000200, "FAN_DELETE"),
+    (0x00000400, "FAN_DELETE_SELF"),
+    (0x00000800, "FAN_MOVE_SELF"),
+    (0x00001000, "FAN_OPEN_EXEC"),
+    (0x00004000, "FAN_Q_OVERFLOW"),
+    (0x00010000, "FAN_OPEN_PERM"),
+    (0x00020000, "FAN_ACCESS_PERM"),
+    (0x00040000, "FAN_OPEN_EXEC_PERM"),
+    (0x08000000, "FAN_EVENT_ON_CHILD"),
+    (0x40000000, "FAN_ONDIR"),
+];
+
+/// 解码 mask 中所有置位的标志，未知位以十六进制表示
+pub fn mask_flags(mask: u64) -> Vec<String> {
+    let mut flags = Vec::new();
+    let mut known = 0u64;
+    for &(bit, name) in MASK_NAMES {
+        known |= bit;
+        if mask & bit != 0 {
+            flags.push(name.to_string());
+        }
+    }
+    let unknown = mask & !known;
+    if unknown != 0 {
+        flags.push(format!("0x{:x}", unknown));
+    }
+    flags
+}
+
 /// 进程路径缓存，用于捕获短暂进程的完整路径
 struct ProcessCache {
     cache: LruCache<i32, String>,
@@ -25,6 +111,11 @@ impl ProcessCache {
         }
     }
     
+    /// 采样模式下只查缓存、不读 /proc，未命中为 "-"
+    fn cached(&self, pid: i32) -> String {
+        self.cache.peek(&pid).cloned().unwrap_or_else(|| "-".to_string())
+    }
+
     /// 获取进程路径，优先从缓存读取
     fn get_or_fetch(&mut self, pid: i32, bin_cache: &process::BinPathCache) -> String {
         // 先查缓存
@@ -64,6 +155,14 @@ struct FanotifyEventMetadata {
     pid: i32,
 }
 
+/// 读线程交给处理线程的事件；fd 的所有权随之转移，由接收方关闭
+#[derive(Clone, Copy)]
+struct RawEvent {
+    fd: RawFd,
+    pid: i32,
+    mask: u64,
+}
+
 extern "C" {
     fn fanotify_init(flags: u32, event_f_flags: u32) -> i32;
     fn fanotify_mark(
@@ -75,38 +174,88 @@ extern "C" {
     ) -> i32;
 }
 
-pub fn start_monitoring(directory: &str, format: &str, verbose: bool) -> Result<()> {
+/// 预检：内核是否支持 fanotify 且当前进程有权限初始化（doctor 使用）
+pub fn probe() -> std::io::Result<()> {
+    let fd = unsafe { fanotify_init(FAN_CLASS_NOTIF, (libc::O_RDONLY | libc::O_CLOEXEC) as u32) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    unsafe { libc::close(fd); }
+    Ok(())
+}
+
+/// mask 是否包含写类事件
+pub fn is_write(mask: u64) -> bool {
+    mask & (FAN_MODIFY | FAN_CLOSE_WRITE) != 0
+}
+
+/// 单条事件元数据大小，--buffer-size 的下限
+pub const METADATA_SIZE: usize = std::mem::size_of::<FanotifyEventMetadata>();
+
+pub fn start_monitoring(
+    args: &MonitorArgs,
+    mask: u64,
+    format: &EventFormat,
+    container_filter: &filter::ContainerFilter,
+    mut aggregator: Option<Box<dyn Aggregator>>,
+) -> Result<()> {
+    let directory = args.directory.as_str();
+    let verbose = args.verbose;
+
     // 设置 Ctrl+C 处理
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
+    // --summary 需要在退出前输出汇总，此时只通知事件循环结束
+    let exit_now = !args.summary;
     ctrlc::set_handler(move || {
         r.store(false, Ordering::SeqCst);
         eprintln!("\nCtrl+C received, exiting...");
-        std::process::exit(0);
+        if exit_now {
+            std::process::exit(0);
+        }
     }).expect("Error setting Ctrl-C handler");
+    let mut summary = args.summary.then(|| {
+        summary::install_sigusr1();
+        summary::Summary::new()
+    });
     
     // 初始化 fanotify (使用 O_NONBLOCK 提高响应速度)
+    // --unlimited-queue：取消内核队列（默认 16384 事件）和 mark 数量上限，需 CAP_SYS_ADMIN
+    let init_flags = if args.unlimited_queue {
+        FAN_CLASS_NOTIF | FAN_UNLIMITED_QUEUE | FAN_UNLIMITED_MARKS
+    } else {
+        FAN_CLASS_NOTIF
+    };
     let fan_fd = unsafe { 
         fanotify_init(
-            FAN_CLASS_NOTIF, 
+            init_flags, 
             (libc::O_RDONLY | libc::O_CLOEXEC | libc::O_NONBLOCK) as u32
         ) 
     };
     if fan_fd < 0 {
+        let err = std::io::Error::last_os_error();
+        // root 却缺 CAP_SYS_ADMIN 时给出原因，而不是笼统地问是否 root
+        let hint = match caps::OwnCaps::read() {
+            Some(own) if unsafe { libc::geteuid() } == 0 && !own.has_effective(caps::CAP_SYS_ADMIN) =>
+                "running as root but CAP_SYS_ADMIN is not in the effective set (see `sedock doctor`)",
+            _ => "Are you running as root?",
+        };
         return Err(SedockerError::Fanotify(
-            "Failed to initialize fanotify. Are you running as root?".to_string()
+            format!("Failed to initialize fanotify: {}. {}", err, hint)
         ));
     }
     
-    // 添加监控标记
+    // 添加监控标记：目录加 FAN_EVENT_ON_CHILD 报告子项事件，单个文件只标记自身
     let dir_cstring = std::ffi::CString::new(directory)
         .map_err(|e| SedockerError::System(format!("Invalid directory path: {}", e)))?;
+    let is_file = std::path::Path::new(directory).is_file();
+    let mark_mask = if is_file { mask } else { mask | FAN_EVENT_ON_CHILD };
     
     let mark_result = unsafe {
         fanotify_mark(
             fan_fd,
             FAN_MARK_ADD,
-            FAN_OPEN | FAN_ACCESS | FAN_MODIFY | FAN_EVENT_ON_CHILD,
+            mark_mask,
             libc::AT_FDCWD,
             dir_cstring.as_ptr(),
         )
@@ -114,12 +263,23 @@ pub fn start_monitoring(directory: &str, format: &str, verbose: bool) -> Result<
     
     if mark_result < 0 {
         return Err(SedockerError::Fanotify(
-            format!("Failed to mark directory: {}", directory)
+            format!("Failed to mark {} {}: {}", if is_file { "file" } else { "directory" },
+                    directory, std::io::Error::last_os_error())
         ));
     }
     
-    // 打印表头
-    if format == "text" {
+    let mut alert_hook = hook::AlertHook::from_args(args)?;
+    let metrics = match &args.metrics_addr {
+        Some(addr) => {
+            let m = Arc::new(metrics::Metrics::default());
+            metrics::serve(addr, m.clone())?;
+            Some(m)
+        }
+        None => None,
+    };
+
+    // 打印表头（聚合模式不逐条输出）
+    if matches!(format, EventFormat::Text) && aggregator.is_none() {
         println!("{:<7} {:<13} {:<5} {:<5} {:<25} {:<15} {}",
                  "EVENT", "PID(H/C)", "UID", "GID", "PROCESS_PATH", "CONTAINER", "FILE_PATH");
         println!("{}", "-".repeat(130));
@@ -133,54 +293,80 @@ pub fn start_monitoring(directory: &str, format: &str, verbose: bool) -> Result<
     };
     
     // 启动时一次性扫描 bin 目录，后续 O(1) 查找
-    let bin_cache = process::BinPathCache::new();
+    let mut bin_cache = process::BinPathCache::new();
+    // --bincache-refresh：按间隔检查 bin 目录 mtime，只重扫有变化的目录
+    let bincache_refresh = args.bincache_refresh.map(|s| std::time::Duration::from_secs(s.max(1)));
+    let mut last_refresh = std::time::Instant::now();
+    let clock = event::Clock::from_arg(&args.clock);
+    let mut names = args.resolve_names.then(names::NameResolver::new);
     // 进程路径缓存（用于捕获短暂进程）
     let mut proc_cache = ProcessCache::new();
+    // --max-rate：超速或积压时降级为最小事件
+    let mut sampler = args.max_rate.map(sampling::Sampler::new);
 
-    
-    // 事件循环（使用更大的缓冲区处理快速事件）
-    let mut buffer = vec![0u8; 16384]; // 4x增大，减少read()调用次数
-    while running.load(Ordering::SeqCst) {
-        let len = unsafe {
-            libc::read(fan_fd, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len())
-        };
-        
-        if len < 0 {
-            let err = std::io::Error::last_os_error();
-            if err.raw_os_error() == Some(libc::EAGAIN) || err.raw_os_error() == Some(libc::EWOULDBLOCK) {
-                // 非阻塞模式下没有数据，短暂休眠避免CPU空转
-                std::thread::sleep(std::time::Duration::from_micros(100));
-                continue;
+    // --warmup：先只填充进程缓存、丢弃事件，避免最初几条输出缺进程路径
+    if let Some(ms) = args.warmup {
+        let mut buffer = vec![0u8; args.buffer_size];
+        let dropped = warmup(fan_fd, &mut buffer, std::time::Duration::from_millis(ms),
+                             &running, &mut proc_cache, &bin_cache);
+        eprintln!("warmup: {}ms, dropped {} events, {} processes cached", ms, dropped, proc_cache.cache.len());
+    }
+
+    // 读线程只负责尽快排空 fan_fd，/proc 解析与输出在当前线程；
+    // 队列满时由读线程丢弃并计数，避免输出阻塞拖到内核队列溢出
+    let (tx, rx) = std::sync::mpsc::sync_channel::<RawEvent>(EVENT_QUEUE_CAPACITY);
+    let queue_drops = AtomicU64::new(0);
+    let mut reported_drops = 0;
+    std::thread::scope(|scope| {
+        scope.spawn(|| read_events(fan_fd, args.buffer_size, &running, tx, &queue_drops, metrics.as_deref()));
+
+        while running.load(Ordering::SeqCst) {
+            if let Some(a) = aggregator.as_mut() {
+                a.flush_if_due();
             }
-            eprintln!("Read error: {}", err);
-            continue;
-        }
-        
-        if len == 0 {
-            continue;
-        }
-        
-        let mut offset = 0;
-        while offset < len as usize {
-            let metadata = unsafe {
-                &*(buffer.as_ptr().add(offset) as *const FanotifyEventMetadata)
-            };
-            
-            if metadata.vers != 3 {
-                eprintln!("Unsupported fanotify version");
-                break;
+            if let Some(every) = bincache_refresh {
+                if last_refresh.elapsed() >= every {
+                    if bin_cache.refresh() && verbose {
+                        eprintln!("bin path cache refreshed");
+                    }
+                    last_refresh = std::time::Instant::now();
+                }
             }
-            
+            if let Some(sm) = summary.as_ref() {
+                if summary::take_request() {
+                    sm.print(format);
+                }
+            }
+            let drops = queue_drops.load(Ordering::Relaxed);
+            if drops > reported_drops {
+                eprintln!("warn: output falling behind, dropped {} events (total {})", drops - reported_drops, drops);
+                reported_drops = drops;
+            }
+
+            let ev = match rx.recv_timeout(std::time::Duration::from_millis(POLL_TIMEOUT_MS as u64)) {
+                Ok(ev) => ev,
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            };
+
             // 获取文件路径
-            let file_path = get_path_from_fd(metadata.fd);
+            let file_path = get_path_from_fd(ev.fd);
+            // 采样模式只输出 pid + 路径；容器过滤生效时仍需读容器 ID
+            let full = sampler.as_mut().is_none_or(|s| s.record(queue_drops.load(Ordering::Relaxed)));
             
             // **FIX: 立即读取进程信息，避免竞态条件**
             // 快速命令(cat/tail/head)可能在处理前就退出
-            let proc_info = match process::get_process_info(metadata.pid, &bin_cache) {
+            // /proc/<pid> 只打开一次，进程信息与容器 ID 共用该句柄
+            let proc_dir = (full || container_filter.is_active()).then(|| process::ProcDir::open(ev.pid));
+            let info_result = match proc_dir.as_ref().filter(|_| full) {
+                Some(Ok(d)) => process::get_process_info(d, &bin_cache),
+                _ => Err(SedockerError::ProcessGone(ev.pid)),
+            };
+            let proc_info = match info_result {
                 Ok(info) => {
                     // 成功读取，同时填充缓存
                     if !info.exe.starts_with('[') {
-                        proc_cache.cache.put(metadata.pid, info.exe.clone());
+                        proc_cache.cache.put(ev.pid, info.exe.clone());
                     }
                     Some(info)
                 }
@@ -190,105 +376,346 @@ pub fn start_monitoring(directory: &str, format: &str, verbose: bool) -> Result<
                 }
                 Err(e) => {
                     eprintln!("Error reading process info: {}", e);
-                    unsafe { libc::close(metadata.fd); }
-                    offset += metadata.event_len as usize;
+                    unsafe { libc::close(ev.fd); }
                     continue;
                 }
             };
             
             // 获取容器信息
-            let container_id = process::get_container_id(metadata.pid);
+            let container_id = proc_dir.as_ref().and_then(|d| d.as_ref().ok()).and_then(process::container_id_in);
+
+            // 容器过滤（include/exclude）
+            if !container_filter.allows(container_id.as_deref()) {
+                unsafe { libc::close(ev.fd); }
+                continue;
+            }
             
+            if let Some(m) = &metrics {
+                m.record(classify(ev.mask), container_id.as_deref());
+            }
+
+            // 聚合模式统计全部访问（不去重），跳过逐条输出
+            if let Some(a) = aggregator.as_mut() {
+                let exe = match &proc_info {
+                    Some(info) => info.exe.clone(),
+                    None if !full => proc_cache.cached(ev.pid),
+                    None => proc_cache.get_or_fetch(ev.pid, &bin_cache),
+                };
+                a.record(&file_path, &exe, container_id.as_deref(), ev.mask);
+                if let Some(sm) = summary.as_mut() {
+                    sm.record(&classify(ev.mask).to_string(), &exe, &file_path);
+                }
+                // 不逐条输出，但 --alert-path 钩子照常触发
+                if let Some(h) = alert_hook.as_mut().filter(|h| h.matches(&file_path)) {
+                    let (container_pid, uid, gid) = proc_info.as_ref()
+                        .map_or((None, 0, 0), |i| (i.container_pid, i.uid, i.gid));
+                    h.fire(&FileAccessEvent {
+                        pid: ev.pid,
+                        container_pid,
+                        uid,
+                        gid,
+                        process_path: exe,
+                        file_path,
+                        container_id,
+                        ..event::stamped(classify(ev.mask), ev.mask)
+                    });
+                }
+                unsafe { libc::close(ev.fd); }
+                continue;
+            }
+
             // 条件去重检查
             let should_process = if let Some(ref mut d) = dedup {
-                !d.is_duplicate(metadata.pid, metadata.mask, &file_path)
+                !d.is_duplicate(ev.pid, ev.mask, &file_path)
             } else {
                 true  // 禁用去重，处理所有事件
             };
+            if !should_process {
+                if let Some(m) = &metrics {
+                    m.record_dedup();
+                }
+            }
             
             if should_process {
                 // 处理事件（传入已读取的进程信息和路径缓存）
-                if let Err(e) = handle_event(metadata, &file_path, format, proc_info, container_id, &mut proc_cache, &bin_cache) {
-                    eprintln!("Error handling event: {}", e);
+                match handle_event(&ev, &file_path, format, clock, full, names.as_mut(), proc_info, container_id, &mut proc_cache, &bin_cache) {
+                    Ok(event) => {
+                        if let Some(h) = alert_hook.as_mut() {
+                            h.fire(&event);
+                        }
+                        if let Some(sm) = summary.as_mut() {
+                            sm.record(&event.event_type, &event.process_path, &event.file_path);
+                        }
+                    }
+                    Err(e) => eprintln!("Error handling event: {}", e),
                 }
             }
-            
+
             // 关闭文件描述符
-            unsafe { libc::close(metadata.fd); }
-            
-            offset += metadata.event_len as usize;
+            unsafe { libc::close(ev.fd); }
         }
-    }
+
+        // 退出：关闭仍在队列中的事件 fd，读线程随后在 poll 超时后结束
+        for ev in rx.try_iter() {
+            unsafe { libc::close(ev.fd); }
+        }
+        drop(rx);
+    });
+    
     
     // 清理
+    if let Some(a) = aggregator.as_mut() {
+        a.flush();
+    }
     unsafe { libc::close(fan_fd); }
-    if format == "text" {
+    if let Some(sm) = summary.as_ref() {
+        sm.print(format);
+    }
+    if matches!(format, EventFormat::Text) {
         eprintln!("\nMonitoring stopped.");
     }
     
     Ok(())
 }
 
+/// 读线程：poll + read 排空 fan_fd，逐条投递到有界队列；队列满时关闭 fd 并计入 drops
+fn read_events(
+    fan_fd: RawFd,
+    buffer_size: usize,
+    running: &AtomicBool,
+    tx: SyncSender<RawEvent>,
+    drops: &AtomicU64,
+    metrics: Option<&metrics::Metrics>,
+) {
+    let mut buffer = vec![0u8; buffer_size];
+    while running.load(Ordering::SeqCst) {
+        let mut pfd = libc::pollfd { fd: fan_fd, events: libc::POLLIN, revents: 0 };
+        let ready = unsafe { libc::poll(&mut pfd, 1, POLL_TIMEOUT_MS) };
+        if ready < 0 {
+            let err = std::io::Error::last_os_error();
+            if err.raw_os_error() != Some(libc::EINTR) {
+                eprintln!("Poll error: {}", err);
+            }
+            continue;
+        }
+        if ready == 0 {
+            continue;
+        }
+
+        let len = unsafe {
+            libc::read(fan_fd, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len())
+        };
+        if len < 0 {
+            let err = std::io::Error::last_os_error();
+            if err.raw_os_error() == Some(libc::EAGAIN) || err.raw_os_error() == Some(libc::EWOULDBLOCK) {
+                continue;
+            }
+            eprintln!("Read error: {}", err);
+            continue;
+        }
+
+        let mut offset = 0;
+        while offset < len as usize {
+            let metadata = unsafe {
+                &*(buffer.as_ptr().add(offset) as *const FanotifyEventMetadata)
+            };
+            if metadata.vers != 3 {
+                eprintln!("Unsupported fanotify version");
+                break;
+            }
+
+            // 内核队列溢出：无 fd 的提示事件，之前的事件已丢失
+            if metadata.mask & FAN_Q_OVERFLOW != 0 {
+                eprintln!("warn: fanotify queue overflow, events were lost (try --unlimited-queue)");
+                if let Some(m) = metrics {
+                    m.record_overflow();
+                }
+            } else {
+                let ev = RawEvent { fd: metadata.fd, pid: metadata.pid, mask: metadata.mask };
+                match tx.try_send(ev) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(ev)) => {
+                        unsafe { libc::close(ev.fd); }
+                        drops.fetch_add(1, Ordering::Relaxed);
+                        if let Some(m) = metrics {
+                            m.record_queue_drop();
+                        }
+                    }
+                    Err(TrySendError::Disconnected(ev)) => {
+                        unsafe { libc::close(ev.fd); }
+                        return;
+                    }
+                }
+            }
+            offset += metadata.event_len as usize;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn handle_event(
-    metadata: &FanotifyEventMetadata,
+    metadata: &RawEvent,
     file_path: &str,
-    format: &str,
+    format: &EventFormat,
+    clock: event::Clock,
+    full: bool,
+    names: Option<&mut names::NameResolver>,
     proc_info: Option<crate::utils::ProcessInfo>,
     container_id: Option<String>,
     proc_cache: &mut ProcessCache,
     bin_cache: &process::BinPathCache,
-) -> Result<()> {
-    // 确定事件类型
-    let event_type = if metadata.mask & FAN_MODIFY != 0 {
-        EventType::Write
-    } else if metadata.mask & FAN_OPEN != 0 {
-        EventType::Open
-    } else {
-        EventType::Read
-    };
+) -> Result<FileAccessEvent> {
+    let event_type = classify(metadata.mask);
     
     // 处理进程信息
-    let (container_pid, uid, gid, exe) = if let Some(info) = proc_info {
-        (info.container_pid, info.uid, info.gid, info.exe)
+    let (container_pid, uid, gid, exe, script_path) = if let Some(info) = proc_info {
+        (info.container_pid, info.uid, info.gid, info.exe, info.script_path)
+    } else if !full {
+        // 采样模式：不读 /proc，进程路径只取缓存
+        (None, 0, 0, proc_cache.cached(metadata.pid), None)
     } else {
         // 进程已退出，从缓存获取路径
-        (None, 0, 0, proc_cache.get_or_fetch(metadata.pid, bin_cache))
+        (None, 0, 0, proc_cache.get_or_fetch(metadata.pid, bin_cache), None)
     };
     
+    let (user, group) = match names.filter(|_| full) {
+        Some(r) => r.resolve(metadata.pid, container_id.as_deref(), uid, gid),
+        None => (None, None),
+    };
+    let exe_in_container = if full && container_id.is_some() {
+        process::get_exe_in_container(metadata.pid, &exe)
+    } else {
+        None
+    };
+    // fd 尚未关闭，fstat 取设备号/inode 以区分“原地改写”与“替换文件”
+    let (dev, ino) = fd_dev_ino(metadata.fd).unwrap_or((0, 0));
+
     // 创建事件
-    let event = event::create_event(
-        event_type,
-        metadata.pid,
+    let event = FileAccessEvent {
+        clock_secs: clock.now_secs(),
+        pid: metadata.pid,
         container_pid,
         uid,
         gid,
-        exe,
-        file_path.to_string(),
-        container_id.clone(),
-    );
+        user,
+        group,
+        process_path: exe,
+        exe_in_container,
+        script_path,
+        file_path: file_path.to_string(),
+        container_id: container_id.clone(),
+        dev,
+        ino,
+        ..event::stamped(event_type, metadata.mask)
+    };
     
     // 输出事件
-    if format == "json" {
-        println!("{}", serde_json::to_string(&event).unwrap());
-    } else {
-        // 格式化 PID 显示
-        let pid_display = if let Some(cpid) = event.container_pid {
-            format!("{}/{}", event.pid, cpid)
-        } else {
-            format!("{}", event.pid)
-        };
-        
-        println!("[{:<5}] {:<13} {:<5} {:<5} {:<25} {:<15} {}",
-                 event.event_type,
-                 pid_display,
-                 event.uid,
-                 event.gid,
-                 truncate_string(&event.process_path, 25),
-                 container_id.as_deref().unwrap_or("-"),
-                 event.file_path);
+    match format {
+        EventFormat::Json => println!("{}", serde_json::to_string(&event).unwrap()),
+        EventFormat::Template(t) => println!("{}", t.render(&event)),
+        EventFormat::Text => {
+            // 格式化 PID 显示
+            let pid_display = if let Some(cpid) = event.container_pid {
+                format!("{}/{}", event.pid, cpid)
+            } else {
+                format!("{}", event.pid)
+            };
+            
+            // 执行事件高亮，便于在混合输出中找出"运行了什么"
+            let marker = if metadata.mask & FAN_OPEN_EXEC != 0 { "  ▶ executed" } else { "" };
+            // 非 wall 时钟时按 dmesg 格式前置时间戳，便于与内核日志对齐
+            if let Some(secs) = event.clock_secs {
+                print!("[{:>12.6}] ", secs);
+            }
+            // 解析到名称时显示为 name(id)
+            let id_display = |name: &Option<String>, id: u32| match name {
+                Some(n) => format!("{}({})", n, id),
+                None => id.to_string(),
+            };
+            println!("[{:<5}] {:<13} {:<5} {:<5} {:<25} {:<15} {}{}",
+                     event.event_type,
+                     pid_display,
+                     id_display(&event.user, event.uid),
+                     id_display(&event.group, event.gid),
+                     truncate_string(event.script_path.as_deref()
+                         .or(event.exe_in_container.as_deref())
+                         .unwrap_or(&event.process_path), 25),
+                     container_id.as_deref().unwrap_or("-"),
+                     event.file_path,
+                     marker);
+        }
     }
     
-    Ok(())
+    Ok(event)
+}
+
+/// 预热阶段：读取并丢弃事件，只把触发进程写入 ProcessCache；返回丢弃的事件数
+fn warmup(
+    fan_fd: RawFd,
+    buffer: &mut [u8],
+    duration: std::time::Duration,
+    running: &AtomicBool,
+    proc_cache: &mut ProcessCache,
+    bin_cache: &process::BinPathCache,
+) -> usize {
+    let deadline = std::time::Instant::now() + duration;
+    let mut dropped = 0;
+    while running.load(Ordering::SeqCst) {
+        let left = deadline.saturating_duration_since(std::time::Instant::now());
+        if left.is_zero() {
+            break;
+        }
+        let timeout = left.as_millis().min(POLL_TIMEOUT_MS as u128) as libc::c_int;
+        let mut pfd = libc::pollfd { fd: fan_fd, events: libc::POLLIN, revents: 0 };
+        if unsafe { libc::poll(&mut pfd, 1, timeout) } <= 0 {
+            continue;
+        }
+        let len = unsafe {
+            libc::read(fan_fd, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len())
+        };
+        if len <= 0 {
+            continue;
+        }
+        let mut offset = 0;
+        while offset < len as usize {
+            let metadata = unsafe {
+                &*(buffer.as_ptr().add(offset) as *const FanotifyEventMetadata)
+            };
+            if metadata.vers != 3 || metadata.event_len == 0 {
+                break;
+            }
+            if metadata.mask & FAN_Q_OVERFLOW == 0 {
+                proc_cache.get_or_fetch(metadata.pid, bin_cache);
+                unsafe { libc::close(metadata.fd); }
+                dropped += 1;
+            }
+            offset += metadata.event_len as usize;
+        }
+    }
+    dropped
+}
+
+/// 确定事件类型（执行优先：同一事件常同时带 FAN_OPEN）
+fn classify(mask: u64) -> EventType {
+    if mask & FAN_OPEN_EXEC != 0 {
+        EventType::Exec
+    } else if mask & (FAN_MODIFY | FAN_CLOSE_WRITE) != 0 {
+        EventType::Write
+    } else if mask & FAN_OPEN != 0 {
+        EventType::Open
+    } else if mask & FAN_CLOSE_NOWRITE != 0 {
+        EventType::Close
+    } else {
+        EventType::Read
+    }
+}
+
+fn fd_dev_ino(fd: RawFd) -> Option<(u64, u64)> {
+    let mut st: libc::stat = unsafe { std::mem::zeroed() };
+    if unsafe { libc::fstat(fd, &mut st) } != 0 {
+        return None;
+    }
+    Some((st.st_dev, st.st_ino))
 }
 
 fn get_path_from_fd(fd: RawFd) -> String {
@@ -0,0 +1,52 @@
+//! 按容器过滤监控事件
+//!
+//! 匹配规则（容器 ID 前缀匹配）：
+//! - exclude 优先级最高：命中 exclude 的容器事件一律丢弃
+//! - 指定了 include 或 --include-host 时进入白名单模式：
+//!   容器事件需命中 include，宿主机事件需开启 include_host
+//! - 均未指定时放行所有事件
+
+use crate::cli::MonitorArgs;
+
+pub struct ContainerFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    include_host: bool,
+}
+
+impl ContainerFilter {
+    pub fn from_args(args: &MonitorArgs) -> Self {
+        Self {
+            include: args.include_containers.clone(),
+            exclude: args.exclude_containers.clone(),
+            include_host: args.include_host,
+        }
+    }
+
+    /// 是否处于白名单模式
+    fn allowlist_active(&self) -> bool {
+        !self.include.is_empty() || self.include_host
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.allowlist_active() || !self.exclude.is_empty()
+    }
+
+    /// 判断事件是否应输出；container_id 为 None 表示宿主机进程
+    pub fn allows(&self, container_id: Option<&str>) -> bool {
+        match container_id {
+            None => !self.allowlist_active() || self.include_host,
+            Some(id) => {
+                if self.exclude.iter().any(|p| id_matches(id, p)) {
+                    return false;
+                }
+                !self.allowlist_active() || self.include.iter().any(|p| id_matches(id, p))
+            }
+        }
+    }
+}
+
+/// 前缀匹配；cgroup 解析出的是短 ID，用户给出完整 ID 时反向匹配
+fn id_matches(id: &str, pattern: &str) -> bool {
+    !pattern.is_empty() && (id.starts_with(pattern) || pattern.starts_with(id))
+}
@@ -0,0 +1,96 @@
+//! 告警钩子：事件路径命中 --alert-path 时执行 --exec 命令
+//!
+//! 事件字段通过环境变量传入（SEDOCK_FILE / SEDOCK_PID / SEDOCK_EVENT / SEDOCK_CONTAINER 等）。
+//! 子进程分离运行，不等待其结束；同时运行的钩子数超过上限时丢弃本次触发。
+
+use crate::cli::MonitorArgs;
+use crate::utils::{FileAccessEvent, Result, SedockerError};
+use std::process::{Child, Command, Stdio};
+
+pub struct AlertHook {
+    patterns: Vec<String>,
+    command: String,
+    max_concurrent: usize,
+    running: Vec<Child>,
+}
+
+impl AlertHook {
+    /// --alert-path 与 --exec 需同时指定
+    pub fn from_args(args: &MonitorArgs) -> Result<Option<Self>> {
+        match (args.alert_paths.is_empty(), &args.exec) {
+            (true, None) => Ok(None),
+            (false, Some(cmd)) => Ok(Some(Self {
+                patterns: args.alert_paths.clone(),
+                command: cmd.clone(),
+                max_concurrent: args.max_hooks.max(1),
+                running: Vec::new(),
+            })),
+            _ => Err(SedockerError::System(
+                "--alert-path and --exec must be used together".to_string()
+            )),
+        }
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        // 不含 '/' 的模式只匹配文件名，如 "*.key"
+        let basename = path.rsplit('/').next().unwrap_or(path);
+        self.patterns.iter().any(|p| {
+            if p.contains('/') { wildcard_match(p, path) } else { wildcard_match(p, basename) }
+        })
+    }
+
+    pub fn fire(&mut self, event: &FileAccessEvent) {
+        if !self.matches(&event.file_path) {
+            return;
+        }
+
+        // 回收已结束的钩子
+        self.running.retain_mut(|c| matches!(c.try_wait(), Ok(None)));
+        if self.running.len() >= self.max_concurrent {
+            eprintln!("warn: {} hooks still running, skipping hook for {}",
+                self.running.len(), event.file_path);
+            return;
+        }
+
+        let spawned = Command::new("sh")
+            .args(["-c", &self.command])
+            .env("SEDOCK_FILE", &event.file_path)
+            .env("SEDOCK_PID", event.pid.to_string())
+            .env("SEDOCK_EVENT", &event.event_type)
+            .env("SEDOCK_CONTAINER", event.container_id.as_deref().unwrap_or(""))
+            .env("SEDOCK_PROCESS", &event.process_path)
+            .env("SEDOCK_UID", event.uid.to_string())
+            .env("SEDOCK_TIMESTAMP", &event.timestamp)
+            .stdin(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(child) => self.running.push(child),
+            Err(e) => eprintln!("warn: failed to run hook '{}': {}", self.command, e),
+        }
+    }
+}
+
+/// shell 风格通配：'*' 匹配任意字符串（含 '/'），'?' 匹配单个字符
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,71 @@
+//! --top-files：按时间窗口聚合访问最多的文件，替代逐事件输出
+
+use crate::monitor::Aggregator;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+pub struct FileStat {
+    pub count: u64,
+    pub by_process: HashMap<String, u64>,
+}
+
+impl FileStat {
+    /// 访问次数最多的进程
+    fn dominant_process(&self) -> (&str, u64) {
+        self.by_process.iter()
+            .max_by_key(|(_, n)| **n)
+            .map(|(p, n)| (p.as_str(), *n))
+            .unwrap_or(("-", 0))
+    }
+}
+
+pub struct HotFiles {
+    top_n: usize,
+    interval: Duration,
+    window_start: Instant,
+    stats: HashMap<String, FileStat>,
+}
+
+impl HotFiles {
+    pub fn new(top_n: usize, interval_secs: u64) -> Self {
+        Self {
+            top_n,
+            interval: Duration::from_secs(interval_secs.max(1)),
+            window_start: Instant::now(),
+            stats: HashMap::new(),
+        }
+    }
+}
+
+impl Aggregator for HotFiles {
+    fn record(&mut self, path: &str, process: &str, _container_id: Option<&str>, _mask: u64) {
+        let stat = self.stats.entry(path.to_string()).or_default();
+        stat.count += 1;
+        *stat.by_process.entry(process.to_string()).or_insert(0) += 1;
+    }
+
+    fn flush_if_due(&mut self) {
+        if self.window_start.elapsed() >= self.interval {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        let elapsed = self.window_start.elapsed().as_secs();
+        let mut entries: Vec<(&String, &FileStat)> = self.stats.iter().collect();
+        entries.sort_by(|a, b| b.1.count.cmp(&a.1.count).then_with(|| a.0.cmp(b.0)));
+
+        println!("── top {} files ({}s, {} distinct) {}",
+            self.top_n, elapsed, entries.len(), chrono::Local::now().format("%H:%M:%S"));
+        println!("{:>8}  {:<30} FILE_PATH", "COUNT", "TOP PROCESS");
+        for (path, stat) in entries.iter().take(self.top_n) {
+            let (proc_path, n) = stat.dominant_process();
+            println!("{:>8}  {:<30} {}", stat.count, format!("{} ({})", proc_path, n), path);
+        }
+        println!();
+
+        self.stats.clear();
+        self.window_start = Instant::now();
+    }
+}
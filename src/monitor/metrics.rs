@@ -0,0 +1,107 @@
+//! --metrics-addr：Prometheus 文本格式的实时计数器
+//!
+//! 单线程的最小 HTTP 应答器，只响应 GET /metrics；计数由事件循环更新。
+
+use crate::utils::{EventType, Result, SedockerError};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 单连接读写超时：应答线程是串行的，慢客户端不能卡住后续抓取
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+const EVENT_TYPES: [EventType; 5] = [
+    EventType::Open, EventType::Read, EventType::Write, EventType::Close, EventType::Exec,
+];
+
+#[derive(Default)]
+pub struct Metrics {
+    by_type: [AtomicU64; EVENT_TYPES.len()],
+    by_container: Mutex<BTreeMap<String, u64>>,   // 容器 ID（宿主机进程为 "host"）
+    deduplicated: AtomicU64,
+    overflows: AtomicU64,
+    queue_drops: AtomicU64,
+}
+
+impl Metrics {
+    pub fn record(&self, event_type: EventType, container_id: Option<&str>) {
+        if let Some(i) = EVENT_TYPES.iter().position(|t| *t == event_type) {
+            self.by_type[i].fetch_add(1, Ordering::Relaxed);
+        }
+        if let Ok(mut m) = self.by_container.lock() {
+            *m.entry(container_id.unwrap_or("host").to_string()).or_insert(0) += 1;
+        }
+    }
+
+    pub fn record_dedup(&self) {
+        self.deduplicated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_overflow(&self) {
+        self.overflows.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_queue_drop(&self) {
+        self.queue_drops.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP sedock_events_total File access events seen, by type.\n");
+        out.push_str("# TYPE sedock_events_total counter\n");
+        for (t, n) in EVENT_TYPES.iter().zip(&self.by_type) {
+            out.push_str(&format!("sedock_events_total{{type=\"{}\"}} {}\n",
+                t.to_string().to_lowercase(), n.load(Ordering::Relaxed)));
+        }
+        out.push_str("# HELP sedock_events_by_container_total File access events seen, by container ID (host = non-container).\n");
+        out.push_str("# TYPE sedock_events_by_container_total counter\n");
+        if let Ok(m) = self.by_container.lock() {
+            for (id, n) in m.iter() {
+                out.push_str(&format!("sedock_events_by_container_total{{container=\"{}\"}} {}\n", id, n));
+            }
+        }
+        out.push_str("# HELP sedock_events_deduplicated_total Events suppressed by deduplication.\n");
+        out.push_str("# TYPE sedock_events_deduplicated_total counter\n");
+        out.push_str(&format!("sedock_events_deduplicated_total {}\n", self.deduplicated.load(Ordering::Relaxed)));
+        out.push_str("# HELP sedock_queue_overflows_total Kernel event queue overflows (events were lost).\n");
+        out.push_str("# TYPE sedock_queue_overflows_total counter\n");
+        out.push_str(&format!("sedock_queue_overflows_total {}\n", self.overflows.load(Ordering::Relaxed)));
+        out.push_str("# HELP sedock_events_dropped_total Events dropped because output fell behind the reader.\n");
+        out.push_str("# TYPE sedock_events_dropped_total counter\n");
+        out.push_str(&format!("sedock_events_dropped_total {}\n", self.queue_drops.load(Ordering::Relaxed)));
+        out
+    }
+}
+
+/// 绑定地址并在后台线程中逐个处理连接；绑定失败在启动时报错
+pub fn serve(addr: &str, metrics: Arc<Metrics>) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .map_err(|e| SedockerError::System(format!("--metrics-addr {}: {}", addr, e)))?;
+    eprintln!("Serving metrics on http://{}/metrics", addr);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = respond(stream, &metrics);
+        }
+    });
+    Ok(())
+}
+
+fn respond(mut stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut request_line = String::new();
+    BufReader::new((&stream).take(8192)).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+
+    let (status, body) = if path == "/metrics" {
+        ("200 OK", metrics.render())
+    } else {
+        ("404 Not Found", "not found\n".to_string())
+    };
+    write!(stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, body.len(), body)
+}
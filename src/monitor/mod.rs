@@ -1,16 +1,55 @@
 pub mod fanotify;
+pub mod filter;
+pub mod hook;
+pub mod hotfiles;
+pub mod metrics;
+pub mod names;
 pub mod process;
+pub mod sampling;
+pub mod summary;
 pub mod event;
+pub mod template;
 
+use crate::cli::MonitorArgs;
 use crate::utils::Result;
 
-pub fn run_monitor(directory: &str, format: &str, verbose: bool) -> Result<()> {
-    // 验证目录存在
-    if !std::path::Path::new(directory).exists() {
+/// 事件输出方式
+pub enum EventFormat {
+    Text,
+    Json,
+    Template(template::Template),
+}
+
+/// 聚合模式：事件不逐条输出，而是累积后按窗口输出（--top-files、trace）
+pub trait Aggregator {
+    fn record(&mut self, path: &str, process: &str, container_id: Option<&str>, mask: u64);
+    /// 窗口到期时输出并重置
+    fn flush_if_due(&mut self);
+    fn flush(&mut self);
+}
+
+pub fn run_monitor(args: &MonitorArgs) -> Result<()> {
+    let aggregator = args.top_files
+        .map(|n| Box::new(hotfiles::HotFiles::new(n, args.interval)) as Box<dyn Aggregator>);
+    run_with(args, aggregator)
+}
+
+/// 监控入口；aggregator 为 Some 时事件交给聚合器而不逐条输出
+pub fn run_with(args: &MonitorArgs, aggregator: Option<Box<dyn Aggregator>>) -> Result<()> {
+    let directory = args.directory.as_str();
+
+    // 验证目标存在：目录或普通文件
+    let path = std::path::Path::new(directory);
+    if !path.exists() {
         return Err(crate::utils::SedockerError::System(
             format!("Directory does not exist: {}", directory)
         ));
     }
+    if !path.is_dir() && !path.is_file() {
+        return Err(crate::utils::SedockerError::System(
+            format!("Not a directory or regular file: {}", directory)
+        ));
+    }
     
     // 检查权限
     if unsafe { libc::geteuid() } != 0 {
@@ -19,12 +58,36 @@ pub fn run_monitor(directory: &str, format: &str, verbose: bool) -> Result<()> {
         ));
     }
     
-    println!("Starting file access monitor on: {}", directory);
-    if verbose {
+    if args.buffer_size < fanotify::METADATA_SIZE {
+        return Err(crate::utils::SedockerError::System(format!(
+            "--buffer-size {} is smaller than one event record ({} bytes)",
+            args.buffer_size, fanotify::METADATA_SIZE)));
+    }
+
+    let mask = fanotify::parse_mask(&args.mask)?;
+    // 模板在启动时解析，字段名错误立即报错
+    let event_format = match &args.template {
+        Some(spec) => EventFormat::Template(template::Template::parse(spec)?),
+        None if args.format.is_json() => EventFormat::Json,
+        None => EventFormat::Text,
+    };
+
+    if path.is_file() {
+        println!("Starting file access monitor on file: {}", directory);
+    } else {
+        println!("Starting file access monitor on: {}", directory);
+    }
+    if args.verbose {
         println!("Deduplication: DISABLED (showing all events)");
     }
+    let container_filter = filter::ContainerFilter::from_args(args);
+    if container_filter.is_active() {
+        println!("Container filter: include={:?} exclude={:?} host={}",
+                 args.include_containers, args.exclude_containers,
+                 if args.include_host { "yes" } else { "no" });
+    }
     println!("Press Ctrl+C to stop\n");
     
     // 启动 fanotify 监控
-    fanotify::start_monitoring(directory, format, verbose)
+    fanotify::start_monitoring(args, mask, &event_format, &container_filter, aggregator)
 }
\ No newline at end of file
@@ -0,0 +1,55 @@
+//! --resolve-names：uid/gid → 用户名/组名
+//!
+//! 宿主机表启动时读取一次；容器表按容器 ID 缓存，首次出现时经
+//! /proc/<pid>/root/etc 读取（不 docker exec，避免在事件热路径上起进程）。
+
+use std::collections::HashMap;
+use std::fs;
+
+#[derive(Default)]
+struct IdTables {
+    users: HashMap<u32, String>,
+    groups: HashMap<u32, String>,
+}
+
+impl IdTables {
+    fn load(etc: &str) -> Self {
+        Self {
+            users: parse_table(&fs::read_to_string(format!("{}/passwd", etc)).unwrap_or_default()),
+            groups: parse_table(&fs::read_to_string(format!("{}/group", etc)).unwrap_or_default()),
+        }
+    }
+}
+
+/// passwd / group 共同格式：name:x:id:...
+fn parse_table(content: &str) -> HashMap<u32, String> {
+    content.lines()
+        .filter_map(|l| {
+            let parts: Vec<&str> = l.split(':').collect();
+            Some((parts.get(2)?.parse().ok()?, parts[0].to_string()))
+        })
+        .collect()
+}
+
+pub struct NameResolver {
+    host: IdTables,
+    containers: HashMap<String, IdTables>,
+}
+
+impl NameResolver {
+    pub fn new() -> Self {
+        Self { host: IdTables::load("/etc"), containers: HashMap::new() }
+    }
+
+    /// 返回 (用户名, 组名)；表中没有的 id 为 None
+    pub fn resolve(&mut self, pid: i32, container_id: Option<&str>, uid: u32, gid: u32)
+        -> (Option<String>, Option<String>)
+    {
+        let tables = match container_id {
+            Some(id) => self.containers.entry(id.to_string())
+                .or_insert_with(|| IdTables::load(&format!("/proc/{}/root/etc", pid))),
+            None => &self.host,
+        };
+        (tables.users.get(&uid).cloned(), tables.groups.get(&gid).cloned())
+    }
+}
@@ -1,12 +1,37 @@
 use crate::utils::{ProcessInfo, Result, SedockerError};
+use crate::utils::id::short_id;
 use std::collections::HashMap;
 use std::fs;
+use std::ffi::CString;
+use std::io::Read;
+use std::os::unix::io::{FromRawFd, RawFd};
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 /// 启动时扫描常见 bin 目录，构建 name→path 查找表
 /// 事件处理时只需 O(1) HashMap 查找，零磁盘 I/O
 pub struct BinPathCache {
     map: HashMap<String, String>,
+    dirs: Vec<BinDir>,   // 按优先级排列，重名时靠前的目录胜出
+}
+
+/// 单个目录的扫描结果；mtime 变化（增删文件）时重新扫描
+struct BinDir {
+    path: String,
+    mtime: Option<SystemTime>,
+    names: Vec<String>,
+}
+
+impl BinDir {
+    fn scan(path: String) -> Self {
+        let mtime = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let names = fs::read_dir(&path)
+            .map(|entries| entries.flatten()
+                .filter_map(|e| e.file_name().to_str().map(String::from))
+                .collect())
+            .unwrap_or_default();
+        Self { path, mtime, names }
+    }
 }
 
 impl BinPathCache {
@@ -27,19 +52,40 @@ impl BinPathCache {
                 }
             }
         }
+        let mut cache = Self {
+            map: HashMap::new(),
+            dirs: dirs.into_iter().map(BinDir::scan).collect(),
+        };
+        cache.rebuild_map();
+        cache
+    }
+
+    /// 只重新扫描 mtime 变化的目录；有变化时重建查找表，返回是否有变化
+    pub fn refresh(&mut self) -> bool {
+        let mut changed = false;
+        for d in &mut self.dirs {
+            let mtime = fs::metadata(&d.path).and_then(|m| m.modified()).ok();
+            if mtime != d.mtime {
+                *d = BinDir::scan(std::mem::take(&mut d.path));
+                changed = true;
+            }
+        }
+        if changed {
+            self.rebuild_map();
+        }
+        changed
+    }
+
+    fn rebuild_map(&mut self) {
         let mut map = HashMap::with_capacity(2048);
-        for dir in &dirs {
-            if let Ok(entries) = fs::read_dir(dir) {
-                for entry in entries.flatten() {
-                    if let Some(name) = entry.file_name().to_str() {
-                        map.entry(name.to_string())
-                            .or_insert_with(|| entry.path().to_string_lossy().into_owned());
-                    }
-                }
+        for d in &self.dirs {
+            for name in &d.names {
+                map.entry(name.clone())
+                    .or_insert_with(|| format!("{}/{}", d.path.trim_end_matches('/'), name));
             }
         }
         map.shrink_to_fit();
-        Self { map }
+        self.map = map;
     }
 
     /// O(1) 查找，找不到返回 None
@@ -56,6 +102,70 @@ impl std::ops::Deref for BinPathCache {
     }
 }
 
+/// 打开的 /proc/<pid> 目录句柄，后续读取均相对于它（openat / readlinkat）
+///
+/// 每个事件只解析一次 /proc 路径；持有句柄期间 PID 即使被回收复用，
+/// 读取也只会返回 ESRCH，而不会读到新进程的信息。
+pub struct ProcDir {
+    pid: i32,
+    fd: RawFd,
+}
+
+impl ProcDir {
+    pub fn open(pid: i32) -> Result<Self> {
+        let path = CString::new(format!("/proc/{}", pid)).unwrap();
+        let fd = unsafe { libc::open(path.as_ptr(), libc::O_RDONLY | libc::O_DIRECTORY | libc::O_CLOEXEC) };
+        if fd < 0 {
+            return Err(proc_error(pid, "", std::io::Error::last_os_error()));
+        }
+        Ok(Self { pid, fd })
+    }
+
+    /// 读取 /proc/<pid>/<name> 的全部内容
+    fn read(&self, name: &str) -> std::io::Result<Vec<u8>> {
+        let cname = CString::new(name).map_err(std::io::Error::other)?;
+        let fd = unsafe { libc::openat(self.fd, cname.as_ptr(), libc::O_RDONLY | libc::O_CLOEXEC) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let mut buf = Vec::new();
+        unsafe { fs::File::from_raw_fd(fd) }.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_string(&self, name: &str) -> std::io::Result<String> {
+        self.read(name).map(|b| String::from_utf8_lossy(&b).into_owned())
+    }
+
+    fn read_link(&self, name: &str) -> std::io::Result<String> {
+        let cname = CString::new(name).map_err(std::io::Error::other)?;
+        let mut buf = vec![0u8; libc::PATH_MAX as usize];
+        let n = unsafe {
+            libc::readlinkat(self.fd, cname.as_ptr(), buf.as_mut_ptr() as *mut libc::c_char, buf.len())
+        };
+        if n < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        buf.truncate(n as usize);
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+}
+
+impl Drop for ProcDir {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd); }
+    }
+}
+
+/// ENOENT / ESRCH 表示进程已退出
+fn proc_error(pid: i32, name: &str, e: std::io::Error) -> SedockerError {
+    if e.kind() == std::io::ErrorKind::NotFound || e.raw_os_error() == Some(libc::ESRCH) {
+        SedockerError::ProcessGone(pid)
+    } else {
+        SedockerError::System(format!("Cannot read /proc/{}/{}: {}", pid, name, e))
+    }
+}
+
 /// 从 PID 获取 UID 和 GID
 #[allow(dead_code)]
 pub fn get_ids_from_pid(pid: i32) -> Result<(u32, u32)> {
@@ -101,45 +211,99 @@ pub fn get_ids_from_pid(pid: i32) -> Result<(u32, u32)> {
 
 /// 获取进程的可执行文件路径（优化版）
 pub fn get_process_path(pid: i32) -> Result<String> {
-    // 方法1: 读取 /proc/{pid}/exe 符号链接（最快且最准确）
-    let exe_link = format!("/proc/{}/exe", pid);
-    if let Ok(path) = fs::read_link(&exe_link) {
-        let path_str = path.to_string_lossy().into_owned();
+    match ProcDir::open(pid) {
+        Ok(dir) => Ok(process_path_in(&dir)),
+        Err(_) => Ok(format!("[{}]", pid)),
+    }
+}
+
+fn process_path_in(dir: &ProcDir) -> String {
+    // 方法1: 读取 exe 符号链接（最快且最准确）
+    if let Ok(path) = dir.read_link("exe") {
         // 移除 " (deleted)" 后缀
-        return Ok(path_str.trim_end_matches(" (deleted)").to_string());
+        return path.trim_end_matches(" (deleted)").to_string();
     }
     
     // 方法2: 从 cmdline 获取（exe失败时）
-    let cmdline_path = format!("/proc/{}/cmdline", pid);
-    if let Ok(content) = fs::read_to_string(&cmdline_path) {
+    if let Ok(content) = dir.read_string("cmdline") {
         if let Some(cmd) = content.split('\0').next() {
             if !cmd.is_empty() {
                 // 绝对路径直接返回
                 if cmd.starts_with('/') {
-                    return Ok(cmd.to_string());
+                    return cmd.to_string();
                 }
                 // 相对路径：只检查最常见的bin目录
                 for prefix in &["/usr/bin/", "/bin/"] {
                     let full_path = format!("{}{}", prefix, cmd);
                     if PathBuf::from(&full_path).exists() {
-                        return Ok(full_path);
+                        return full_path;
                     }
                 }
-                return Ok(cmd.to_string());
+                return cmd.to_string();
             }
         }
     }
     
     // 方法3: 使用 comm（最后手段，返回短名称，由调用方解析完整路径）
-    let comm_path = format!("/proc/{}/comm", pid);
-    if let Ok(content) = fs::read_to_string(&comm_path) {
+    if let Ok(content) = dir.read_string("comm") {
         let name = content.trim();
         if !name.is_empty() {
-            return Ok(name.to_string());
+            return name.to_string();
         }
     }
 
-    Ok(format!("[{}]", pid))
+    format!("[{}]", dir.pid)
+}
+
+/// 容器进程在容器内看到的可执行文件路径
+///
+/// 宿主机侧 exe 可能是 overlay 路径（.../overlay2/<id>/merged/usr/bin/python），
+/// 截掉 merged 前缀；否则若 /proc/<pid>/root 下存在同名路径，说明已是容器内路径。
+pub fn get_exe_in_container(pid: i32, host_exe: &str) -> Option<String> {
+    if let Some(pos) = host_exe.find("/merged/") {
+        return Some(host_exe[pos + "/merged".len()..].to_string());
+    }
+    if host_exe.starts_with('/') && PathBuf::from(format!("/proc/{}/root{}", pid, host_exe)).exists() {
+        return Some(host_exe.to_string());
+    }
+    None
+}
+
+/// 脚本解释器的可执行文件名（前缀匹配，覆盖 python3.11 / php8.2 等带版本号的名称）
+const INTERPRETERS: &[&str] = &[
+    "python", "perl", "ruby", "node", "php", "lua", "bash", "sh", "dash", "zsh", "ash", "ksh",
+];
+
+/// exe 为解释器时，从 cmdline 取第一个非选项参数作为脚本路径
+///
+/// `python -m mod` / `sh -c '...'` 这类没有脚本文件的调用返回 None；
+/// 相对路径按进程 cwd 补全。
+fn script_path_in(dir: &ProcDir, exe: &str) -> Option<String> {
+    let name = exe.rsplit('/').next().unwrap_or(exe);
+    let is_interpreter = INTERPRETERS.iter().any(|i| {
+        name.strip_prefix(i).is_some_and(|rest| rest.chars().all(|c| c.is_ascii_digit() || c == '.'))
+    });
+    if !is_interpreter {
+        return None;
+    }
+
+    let cmdline = dir.read("cmdline").ok()?;
+    let args: Vec<String> = cmdline.split(|&b| b == 0)
+        .filter(|a| !a.is_empty())
+        .map(|a| String::from_utf8_lossy(a).into_owned())
+        .collect();
+    for arg in args.iter().skip(1) {
+        match arg.as_str() {
+            "-c" | "-m" | "-e" | "-r" | "--eval" => return None,
+            a if a.starts_with('-') => continue,
+            a if a.starts_with('/') => return Some(a.to_string()),
+            a => {
+                let cwd = dir.read_link("cwd").ok()?;
+                return Some(PathBuf::from(cwd).join(a).to_string_lossy().into_owned());
+            }
+        }
+    }
+    None
 }
 
 /// 获取进程名称
@@ -154,8 +318,12 @@ pub fn get_process_comm(pid: i32) -> Result<String> {
 
 /// 检查进程是否在容器中
 pub fn get_container_id(pid: i32) -> Option<String> {
-    let cgroup_path = format!("/proc/{}/cgroup", pid);
-    let content = fs::read_to_string(&cgroup_path).ok()?;
+    container_id_in(&ProcDir::open(pid).ok()?)
+}
+
+/// 与 get_process_info 共用同一个 /proc/<pid> 句柄
+pub fn container_id_in(dir: &ProcDir) -> Option<String> {
+    let content = dir.read_string("cgroup").ok()?;
     
     for line in content.lines() {
         if line.contains("docker") || line.contains("containerd") {
@@ -172,15 +340,15 @@ pub fn get_container_id(pid: i32) -> Option<String> {
 fn extract_container_id(line: &str) -> Option<String> {
     // 从 cgroup 行中提取容器 ID
     // 格式: 12:pids:/docker/1234567890abcdef...
+    // systemd cgroup driver: 0::/system.slice/docker-1234567890abcdef....scope
     if let Some(pos) = line.rfind('/') {
-        let id = &line[pos + 1..];
-        let id = id.trim();
+        let id = line[pos + 1..].trim();
+        let id = id.strip_prefix("docker-").unwrap_or(id);
+        let id = id.strip_suffix(".scope").unwrap_or(id);
         
-        // 取前 12 个字符（短 ID）
-        if id.len() >= 12 {
-            return Some(id[..12].to_string());
-        } else if !id.is_empty() {
-            return Some(id.to_string());
+        // 按 --id-length 截断（默认 12 位短 ID）
+        if !id.is_empty() {
+            return Some(short_id(id));
         }
     }
     
@@ -213,23 +381,11 @@ pub fn get_container_pid(host_pid: i32) -> Option<i32> {
 }
 
 /// 获取完整的进程信息（优化版：只读取一次status）
-pub fn get_process_info(pid: i32, bin_cache: &BinPathCache) -> Result<ProcessInfo> {
+pub fn get_process_info(dir: &ProcDir, bin_cache: &BinPathCache) -> Result<ProcessInfo> {
+    let pid = dir.pid;
     // 一次性读取 status 文件，获取多个字段
-    let status_path = format!("/proc/{}/status", pid);
-    let status_content = fs::read_to_string(&status_path)
-        .map_err(|e| {
-            use std::io::ErrorKind;
-            match e.kind() {
-                ErrorKind::NotFound => SedockerError::ProcessGone(pid),
-                _ => {
-                    if let Some(3) = e.raw_os_error() {
-                        SedockerError::ProcessGone(pid)
-                    } else {
-                        SedockerError::System(format!("Cannot read {}: {}", status_path, e))
-                    }
-                }
-            }
-        })?;
+    let status_content = dir.read_string("status")
+        .map_err(|e| proc_error(pid, "status", e))?;
     
     // 从 status 中解析 uid, gid, container_pid, name
     let mut uid = 0u32;
@@ -260,8 +416,8 @@ pub fn get_process_info(pid: i32, bin_cache: &BinPathCache) -> Result<ProcessInf
         }
     }
     
-    // 获取 exe 路径（仍需单独读取）
-    let exe = get_process_path(pid)?;
+    // 获取 exe 路径（同一句柄上 readlinkat）
+    let exe = process_path_in(dir);
     // 如果 exe 只拿到短名称或 [pid]，通过 BinPathCache O(1) 查找完整路径
     let exe = if !exe.contains('/') {
         // 短名称（来自 comm），尝试解析
@@ -273,12 +429,15 @@ pub fn get_process_info(pid: i32, bin_cache: &BinPathCache) -> Result<ProcessInf
         exe
     };
 
+    let script_path = script_path_in(dir, &exe);
+
     Ok(ProcessInfo {
         pid,
         uid,
         gid,
         comm,
         exe,
+        script_path,
         container_pid,
     })
 }
\ No newline at end of file
@@ -0,0 +1,62 @@
+//! --max-rate：事件来得比完整解析快时降级为最小事件（pid + 路径），
+//! 跳过 exe / uid / 容器解析，避免 /proc 读取拖垮事件循环
+
+use std::time::{Duration, Instant};
+
+/// 速率统计窗口
+const WINDOW: Duration = Duration::from_secs(1);
+
+pub struct Sampler {
+    max_rate: u64,
+    window_start: Instant,
+    count: u64,
+    drops_at_start: u64,
+    degraded: bool,
+}
+
+impl Sampler {
+    pub fn new(max_rate: u64) -> Self {
+        Sampler {
+            max_rate: max_rate.max(1),
+            window_start: Instant::now(),
+            count: 0,
+            drops_at_start: 0,
+            degraded: false,
+        }
+    }
+
+    /// 记录一条事件，`total_drops` 为读线程累计丢弃数（积压信号）；
+    /// 返回 true 表示该事件应完整解析
+    pub fn record(&mut self, total_drops: u64) -> bool {
+        self.count += 1;
+
+        // 窗口内已超限：立即降级，不等窗口结束
+        if !self.degraded && self.count > self.max_rate {
+            self.set_degraded(true, self.count);
+        }
+
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= WINDOW {
+            let rate = (self.count as f64 / elapsed.as_secs_f64()) as u64;
+            let backlog = total_drops > self.drops_at_start;
+            self.set_degraded(rate > self.max_rate || backlog, rate);
+            self.window_start = Instant::now();
+            self.count = 0;
+            self.drops_at_start = total_drops;
+        }
+        !self.degraded
+    }
+
+    fn set_degraded(&mut self, degraded: bool, rate: u64) {
+        if degraded == self.degraded {
+            return;
+        }
+        self.degraded = degraded;
+        if degraded {
+            eprintln!("sampling: ~{} events/s over --max-rate {} or output backlog; emitting pid + path only",
+                rate, self.max_rate);
+        } else {
+            eprintln!("sampling: rate back to ~{} events/s, full enrichment resumed", rate);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! --summary：退出时（以及收到 SIGUSR1 时）输出运行期间的事件汇总
+
+use crate::monitor::EventFormat;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// 汇总中列出的进程 / 文件数
+const SUMMARY_TOP: usize = 10;
+
+/// SIGUSR1 只置位，由事件循环在安全的上下文中输出
+static SUMMARY_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_sigusr1(_: libc::c_int) {
+    SUMMARY_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+pub fn install_sigusr1() {
+    unsafe { libc::signal(libc::SIGUSR1, on_sigusr1 as *const () as libc::sighandler_t); }
+}
+
+/// 取走 SIGUSR1 请求标记
+pub fn take_request() -> bool {
+    SUMMARY_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+pub struct Summary {
+    started: Instant,
+    by_event: BTreeMap<String, u64>,
+    processes: HashMap<String, u64>,
+    files: HashMap<String, u64>,
+}
+
+#[derive(Serialize)]
+struct Ranked<'a> {
+    name: &'a str,
+    count: u64,
+}
+
+#[derive(Serialize)]
+struct SummaryJson<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    runtime_secs: u64,
+    total: u64,
+    by_event: &'a BTreeMap<String, u64>,
+    top_processes: Vec<Ranked<'a>>,
+    top_files: Vec<Ranked<'a>>,
+}
+
+impl Summary {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            by_event: BTreeMap::new(),
+            processes: HashMap::new(),
+            files: HashMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, event_type: &str, process: &str, file: &str) {
+        *self.by_event.entry(event_type.to_string()).or_insert(0) += 1;
+        *self.processes.entry(process.to_string()).or_insert(0) += 1;
+        *self.files.entry(file.to_string()).or_insert(0) += 1;
+    }
+
+    /// JSON 输出为单行对象（type=summary）到 stdout，与逐事件对象同流；其余格式为文本到 stderr
+    pub fn print(&self, format: &EventFormat) {
+        let runtime_secs = self.started.elapsed().as_secs();
+        let total: u64 = self.by_event.values().sum();
+        let top_processes = top(&self.processes);
+        let top_files = top(&self.files);
+
+        if matches!(format, EventFormat::Json) {
+            let json = SummaryJson {
+                kind: "summary",
+                runtime_secs,
+                total,
+                by_event: &self.by_event,
+                top_processes,
+                top_files,
+            };
+            if let Ok(line) = serde_json::to_string(&json) {
+                println!("{}", line);
+            }
+            return;
+        }
+
+        eprintln!("── summary: {} events in {}s", total, runtime_secs);
+        let by_event: Vec<String> = self.by_event.iter().map(|(k, n)| format!("{} {}", k, n)).collect();
+        eprintln!("  by event : {}", by_event.join(", "));
+        eprintln!("  top processes:");
+        for r in &top_processes {
+            eprintln!("    {:>8}  {}", r.count, r.name);
+        }
+        eprintln!("  top files:");
+        for r in &top_files {
+            eprintln!("    {:>8}  {}", r.count, r.name);
+        }
+    }
+}
+
+fn top(map: &HashMap<String, u64>) -> Vec<Ranked<'_>> {
+    let mut v: Vec<Ranked> = map.iter().map(|(k, n)| Ranked { name: k, count: *n }).collect();
+    v.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(b.name)));
+    v.truncate(SUMMARY_TOP);
+    v
+}
@@ -0,0 +1,116 @@
+//! 自定义单行事件格式（--template "{pid} {uid} {file_path}"）
+//!
+//! 字段名即 FileAccessEvent 的 JSON 字段名，启动时校验；
+//! 修饰符：`short`（路径取文件名、容器 ID 按 --id-length 截断）、`upper`、`lower`。
+//! `{{` / `}}` 输出字面量花括号。
+
+use crate::utils::{FileAccessEvent, Result, SedockerError};
+use crate::utils::id::short_id;
+
+const MODIFIERS: &[&str] = &["short", "upper", "lower"];
+
+enum Part {
+    Literal(String),
+    Field { name: String, modifier: Option<String> },
+}
+
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let known = known_fields();
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = spec.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' if chars.peek() == Some(&'{') => { chars.next(); literal.push('{'); }
+                '}' if chars.peek() == Some(&'}') => { chars.next(); literal.push('}'); }
+                '{' => {
+                    let mut inner = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => inner.push(c),
+                            None => return Err(SedockerError::Parse(
+                                format!("unterminated '{{' in template: {}", spec))),
+                        }
+                    }
+                    let (name, modifier) = match inner.split_once(':') {
+                        Some((n, m)) => (n.trim().to_string(), Some(m.trim().to_string())),
+                        None => (inner.trim().to_string(), None),
+                    };
+                    if !known.contains(&name) {
+                        return Err(SedockerError::Parse(format!(
+                            "unknown template field '{}' (valid: {})", name, known.join(", "))));
+                    }
+                    if let Some(m) = &modifier {
+                        if !MODIFIERS.contains(&m.as_str()) {
+                            return Err(SedockerError::Parse(format!(
+                                "unknown template modifier '{}' (valid: {})", m, MODIFIERS.join(", "))));
+                        }
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Field { name, modifier });
+                }
+                '}' => return Err(SedockerError::Parse(
+                    format!("unmatched '}}' in template: {}", spec))),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    pub fn render(&self, event: &FileAccessEvent) -> String {
+        let value = serde_json::to_value(event).unwrap_or_default();
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(s) => out.push_str(s),
+                Part::Field { name, modifier } => {
+                    let raw = value_to_string(&value[name.as_str()]);
+                    out.push_str(&apply_modifier(name, &raw, modifier.as_deref()));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// FileAccessEvent 的所有字段名（随结构体自动更新）
+fn known_fields() -> Vec<String> {
+    serde_json::to_value(FileAccessEvent::default())
+        .ok()
+        .and_then(|v| v.as_object().map(|o| o.keys().cloned().collect()))
+        .unwrap_or_default()
+}
+
+fn value_to_string(v: &serde_json::Value) -> String {
+    match v {
+        serde_json::Value::Null => "-".to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(a) => a.iter()
+            .map(value_to_string)
+            .collect::<Vec<_>>()
+            .join("|"),
+        other => other.to_string(),
+    }
+}
+
+fn apply_modifier(name: &str, raw: &str, modifier: Option<&str>) -> String {
+    match modifier {
+        Some("short") if name.ends_with("_id") => short_id(raw),
+        Some("short") => raw.rsplit('/').next().unwrap_or(raw).to_string(),
+        Some("upper") => raw.to_uppercase(),
+        Some("lower") => raw.to_lowercase(),
+        _ => raw.to_string(),
+    }
+}
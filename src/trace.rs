@@ -0,0 +1,133 @@
+//! trace：持续监控目录，按窗口输出“哪些容器访问了哪些文件”及容器的简要信息
+
+use crate::check::collector;
+use crate::cli::TraceArgs;
+use crate::monitor::{self, fanotify, Aggregator};
+use crate::utils::Result;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// 每个容器最多列出的文件数
+const FILES_PER_CONTAINER: usize = 10;
+
+#[derive(Default)]
+struct PathStat {
+    reads: u64,
+    writes: u64,
+}
+
+/// 容器简要信息（首次出现时 inspect 一次）
+struct ContainerBrief {
+    name: String,
+    image: String,
+    status: String,
+}
+
+struct TraceAggregator {
+    interval: Duration,
+    window_start: Instant,
+    // 容器 ID（None = 宿主机进程）→ 路径 → 计数
+    touched: HashMap<Option<String>, HashMap<String, PathStat>>,
+    processes: HashMap<Option<String>, HashMap<String, u64>>,
+    briefs: HashMap<String, ContainerBrief>,
+}
+
+impl TraceAggregator {
+    fn new(interval_secs: u64) -> Self {
+        Self {
+            interval: Duration::from_secs(interval_secs.max(1)),
+            window_start: Instant::now(),
+            touched: HashMap::new(),
+            processes: HashMap::new(),
+            briefs: HashMap::new(),
+        }
+    }
+
+    fn brief(&mut self, id: &str) -> &ContainerBrief {
+        self.briefs.entry(id.to_string()).or_insert_with(|| {
+            match collector::docker_inspect(id) {
+                Ok(j) => ContainerBrief {
+                    name: j["Name"].as_str().unwrap_or(id).trim_start_matches('/').to_string(),
+                    image: j["Config"]["Image"].as_str().unwrap_or("").to_string(),
+                    status: j["State"]["Status"].as_str().unwrap_or("").to_string(),
+                },
+                Err(_) => ContainerBrief {
+                    name: id.to_string(),
+                    image: "?".to_string(),
+                    status: "gone".to_string(),
+                },
+            }
+        })
+    }
+}
+
+impl Aggregator for TraceAggregator {
+    fn record(&mut self, path: &str, process: &str, container_id: Option<&str>, mask: u64) {
+        let key = container_id.map(String::from);
+        let stat = self.touched.entry(key.clone()).or_default()
+            .entry(path.to_string()).or_default();
+        if fanotify::is_write(mask) {
+            stat.writes += 1;
+        } else {
+            stat.reads += 1;
+        }
+        *self.processes.entry(key).or_default()
+            .entry(process.to_string()).or_insert(0) += 1;
+    }
+
+    fn flush_if_due(&mut self) {
+        if self.window_start.elapsed() >= self.interval {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.touched.is_empty() {
+            self.window_start = Instant::now();
+            return;
+        }
+        let touched = std::mem::take(&mut self.touched);
+        let processes = std::mem::take(&mut self.processes);
+        println!("══ trace {}  ({}s window, {} sources) ══",
+            chrono::Local::now().format("%H:%M:%S"), self.window_start.elapsed().as_secs(), touched.len());
+
+        // 写入量多的容器排前面
+        let mut sources: Vec<(Option<String>, HashMap<String, PathStat>)> = touched.into_iter().collect();
+        sources.sort_by_key(|(_, files)| std::cmp::Reverse(files.values().map(|s| s.writes).sum::<u64>()));
+
+        for (id, files) in sources {
+            let (reads, writes) = files.values().fold((0, 0), |(r, w), s| (r + s.reads, w + s.writes));
+            let header = match &id {
+                Some(id) => {
+                    let b = self.brief(id);
+                    format!("{} ({})  {}  [{}]", b.name, id, b.image, b.status)
+                }
+                None => "host".to_string(),
+            };
+            let mut procs: Vec<(&String, &u64)> = processes.get(&id).map(|m| m.iter().collect()).unwrap_or_default();
+            procs.sort_by_key(|(_, n)| std::cmp::Reverse(**n));
+            let procs: Vec<&str> = procs.iter().take(3).map(|(p, _)| p.as_str()).collect();
+
+            println!("  ▸ {}  — {} files, {} reads, {} writes", header, files.len(), reads, writes);
+            println!("      processes: {}", procs.join(", "));
+
+            let mut files: Vec<(String, PathStat)> = files.into_iter().collect();
+            files.sort_by_key(|(_, s)| std::cmp::Reverse(s.reads + s.writes));
+            for (path, s) in files.iter().take(FILES_PER_CONTAINER) {
+                let mark = if s.writes > 0 { "W" } else { "R" };
+                println!("      {} {:>6}  {}", mark, s.reads + s.writes, path);
+            }
+            if files.len() > FILES_PER_CONTAINER {
+                println!("      ... and {} more", files.len() - FILES_PER_CONTAINER);
+            }
+        }
+        println!();
+
+        self.window_start = Instant::now();
+    }
+}
+
+pub fn run_trace(args: &TraceArgs) -> Result<()> {
+    let aggregator = TraceAggregator::new(args.interval);
+    monitor::run_with(&args.monitor_args(), Some(Box::new(aggregator)))
+}
@@ -0,0 +1,54 @@
+//! 本进程 capability 集合（/proc/self/status 的 CapEff / CapBnd），doctor 与 monitor 共用
+
+/// capability 名称，下标即位号（linux/capability.h）
+const CAP_NAMES: &[&str] = &[
+    "CHOWN", "DAC_OVERRIDE", "DAC_READ_SEARCH", "FOWNER", "FSETID", "KILL", "SETGID", "SETUID",
+    "SETPCAP", "LINUX_IMMUTABLE", "NET_BIND_SERVICE", "NET_BROADCAST", "NET_ADMIN", "NET_RAW",
+    "IPC_LOCK", "IPC_OWNER", "SYS_MODULE", "SYS_RAWIO", "SYS_CHROOT", "SYS_PTRACE", "SYS_PACCT",
+    "SYS_ADMIN", "SYS_BOOT", "SYS_NICE", "SYS_RESOURCE", "SYS_TIME", "SYS_TTY_CONFIG", "MKNOD",
+    "LEASE", "AUDIT_WRITE", "AUDIT_CONTROL", "SETFCAP", "MAC_OVERRIDE", "MAC_ADMIN", "SYSLOG",
+    "WAKE_ALARM", "BLOCK_SUSPEND", "AUDIT_READ", "PERFMON", "BPF", "CHECKPOINT_RESTORE",
+];
+
+/// fanotify_init 需要的 capability 位
+pub const CAP_SYS_ADMIN: u32 = 21;
+
+/// 本进程的有效集与边界集
+pub struct OwnCaps {
+    pub effective: u64,
+    pub bounding: u64,
+}
+
+impl OwnCaps {
+    pub fn read() -> Option<Self> {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let field = |key: &str| status.lines()
+            .find_map(|l| l.strip_prefix(key))
+            .and_then(|v| u64::from_str_radix(v.trim(), 16).ok());
+        Some(OwnCaps { effective: field("CapEff:")?, bounding: field("CapBnd:")? })
+    }
+
+    pub fn has_effective(&self, bit: u32) -> bool {
+        self.effective & (1 << bit) != 0
+    }
+}
+
+/// 内核支持的全部 capability 位（/proc/sys/kernel/cap_last_cap）
+pub fn kernel_full_mask() -> Option<u64> {
+    let last: u32 = std::fs::read_to_string("/proc/sys/kernel/cap_last_cap").ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(if last >= 63 { u64::MAX } else { (1u64 << (last + 1)) - 1 })
+}
+
+/// 掩码 → 名称列表；名称表之外的位记为 cap_<n>
+pub fn cap_names(mask: u64) -> Vec<String> {
+    (0..64)
+        .filter(|bit| mask & (1u64 << bit) != 0)
+        .map(|bit| match CAP_NAMES.get(bit) {
+            Some(name) => name.to_string(),
+            None => format!("cap_{}", bit),
+        })
+        .collect()
+}
@@ -0,0 +1,106 @@
+//! 两个子命令共用的输出格式，在 CLI 解析阶段校验
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Jsonl,
+    Influx,
+}
+
+impl OutputFormat {
+    pub const ALL: &'static [OutputFormat] = &[
+        OutputFormat::Text,
+        OutputFormat::Json,
+        OutputFormat::Jsonl,
+        OutputFormat::Influx,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Text   => "text",
+            OutputFormat::Json   => "json",
+            OutputFormat::Jsonl  => "jsonl",
+            OutputFormat::Influx => "influx",
+        }
+    }
+
+    /// json / jsonl 均为机器可读的 JSON
+    pub fn is_json(&self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Jsonl)
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        Self::ALL.iter()
+            .copied()
+            .find(|f| f.as_str() == lower)
+            .ok_or_else(|| format!("unknown format '{}' (valid: {})", s, valid_list(Self::ALL)))
+    }
+}
+
+/// monitor 按事件逐行输出，只支持 text / json / jsonl
+pub fn parse_monitor_format(s: &str) -> Result<OutputFormat, String> {
+    const MONITOR: &[OutputFormat] = &[OutputFormat::Text, OutputFormat::Json, OutputFormat::Jsonl];
+    match s.parse::<OutputFormat>() {
+        Ok(f) if MONITOR.contains(&f) => Ok(f),
+        _ => Err(format!("unknown format '{}' for monitor (valid: {})", s, valid_list(MONITOR))),
+    }
+}
+
+fn valid_list(formats: &[OutputFormat]) -> String {
+    formats.iter().map(|f| f.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+/// 文本输出的容量单位：binary（KiB/MiB/GiB，与 docker 一致）或 si（kB/MB/GB）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    Binary,
+    Si,
+}
+
+impl Units {
+    /// 按所选进制格式化字节数
+    pub fn format_bytes(&self, b: u64) -> String {
+        let (base, suffixes) = match self {
+            Units::Binary => (1024.0, ["KiB", "MiB", "GiB", "TiB"]),
+            Units::Si     => (1000.0, ["kB", "MB", "GB", "TB"]),
+        };
+        let mut value = b as f64;
+        if value < base {
+            return format!("{}B", b);
+        }
+        let mut unit = 0;
+        value /= base;
+        while value >= base && unit + 1 < suffixes.len() {
+            value /= base;
+            unit += 1;
+        }
+        format!("{:.1}{}", value, suffixes[unit])
+    }
+}
+
+impl FromStr for Units {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "binary" => Ok(Units::Binary),
+            "si"     => Ok(Units::Si),
+            _ => Err(format!("unknown units '{}' (valid: binary, si)", s)),
+        }
+    }
+}
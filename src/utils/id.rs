@@ -0,0 +1,22 @@
+//! 容器 ID 显示长度（--id-length），check / events / monitor 统一经 short_id 截断
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// docker 默认短 ID 长度，也是下限：截断后的 ID 还用于 stats 关联、docker top 参数
+/// 和 cgroup 匹配，更短的前缀容易指向别的容器
+pub const DEFAULT_ID_LENGTH: usize = 12;
+
+/// 完整 ID（sha256 十六进制）长度
+pub const MAX_ID_LENGTH: usize = 64;
+
+static ID_LENGTH: AtomicUsize = AtomicUsize::new(DEFAULT_ID_LENGTH);
+
+/// 启动时按 --id-length 设置一次
+pub fn set_id_length(n: usize) {
+    ID_LENGTH.store(n.clamp(DEFAULT_ID_LENGTH, MAX_ID_LENGTH), Ordering::Relaxed);
+}
+
+/// 截断到当前 ID 长度；短于该长度的原样返回
+pub fn short_id(id: &str) -> String {
+    id.chars().take(ID_LENGTH.load(Ordering::Relaxed)).collect()
+}
@@ -1,5 +1,10 @@
+pub mod caps;
 pub mod error;
+pub mod format;
+pub mod id;
+pub mod time;
 pub mod types;
 
 pub use error::{Result, SedockerError};
+pub use format::{OutputFormat, Units};
 pub use types::*;
\ No newline at end of file
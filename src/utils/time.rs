@@ -0,0 +1,27 @@
+//! 时间戳解析与时长格式化（check / monitor 共用）
+
+/// RFC3339 时间戳距今秒数；docker 的零值 "0001-01-01T00:00:00Z" 视为无效
+pub fn seconds_since(ts: &str) -> Option<i64> {
+    let t = parse_timestamp(ts)?;
+    Some((chrono::Utc::now() - t).num_seconds().max(0))
+}
+
+/// 解析 RFC3339；docker 零值返回 None
+pub fn parse_timestamp(ts: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let t = chrono::DateTime::parse_from_rfc3339(ts).ok()?;
+    (t.timestamp() > 0).then(|| t.with_timezone(&chrono::Utc))
+}
+
+/// 秒数 → "3d 4h 5m" / "4h 5m" / "5m"
+pub fn format_duration(seconds: u64) -> String {
+    let d = seconds / 86400;
+    let h = (seconds % 86400) / 3600;
+    let m = (seconds % 3600) / 60;
+    if d > 0 {
+        format!("{}d {}h {}m", d, h, m)
+    } else if h > 0 {
+        format!("{}h {}m", h, m)
+    } else {
+        format!("{}m", m)
+    }
+}
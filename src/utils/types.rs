@@ -8,6 +8,7 @@ pub struct ProcessInfo {
     pub container_pid: Option<i32>,
     pub comm: String,
     pub exe: String,
+    pub script_path: Option<String>,   // exe 为解释器时 cmdline 中的脚本
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,6 +16,8 @@ pub enum EventType {
     Open,
     Read,
     Write,
+    Close,
+    Exec,
     #[allow(dead_code)]
     Modify,
 }
@@ -25,20 +28,31 @@ impl std::fmt::Display for EventType {
             EventType::Open => write!(f, "OPEN"),
             EventType::Read => write!(f, "READ"),
             EventType::Write => write!(f, "WRITE"),
+            EventType::Close => write!(f, "CLOSE"),
+            EventType::Exec => write!(f, "EXEC"),
             EventType::Modify => write!(f, "MODIFY"),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FileAccessEvent {
     pub event_type: String,
     pub timestamp: String,
+    pub clock_secs: Option<f64>,   // --clock monotonic/boot：开机以来的秒数（wall 时为 None）
     pub pid: i32,
     pub container_pid: Option<i32>,
     pub uid: u32,
     pub gid: u32,
+    pub user: Option<String>,    // --resolve-names：容器进程按容器内 passwd/group 解析
+    pub group: Option<String>,
     pub process_path: String,
+    pub exe_in_container: Option<String>,   // 容器内视角的可执行文件路径（仅容器进程）
+    pub script_path: Option<String>,        // 解释器进程正在运行的脚本（python foo.py → foo.py）
     pub file_path: String,
     pub container_id: Option<String>,
+    pub dev: u64,               // 被访问文件的 st_dev / st_ino（fstat 失败时为 0）
+    pub ino: u64,
+    pub mask: u64,              // 原始 fanotify mask
+    pub flags: Vec<String>,     // mask 中所有置位的标志名
 }
\ No newline at end of file